@@ -0,0 +1,43 @@
+//! synchronous wrapper around the async [`crate::AnityaClient`]
+//!
+//! The blocking client owns a single-threaded tokio runtime, which is used to drive requests to
+//! completion. Note that its methods must not be called from within an async context.
+
+use serde::de::DeserializeOwned;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::client::ClientBuildError;
+use crate::errors::QueryError;
+use crate::request::{PaginatedRequest, Pagination, SingleRequest};
+
+#[derive(Debug)]
+pub struct AnityaClient {
+    inner: crate::AnityaClient,
+    runtime: Runtime,
+}
+
+impl AnityaClient {
+    pub fn new(client: crate::AnityaClient) -> Result<Self, ClientBuildError> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(AnityaClient { inner: client, runtime })
+    }
+
+    pub fn request<R, P, T>(&self, request: &R) -> Result<T, QueryError>
+    where
+        R: SingleRequest<P, T>,
+        T: DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner.request(request))
+    }
+
+    pub fn paginated_request<R, S, P, V, T>(&self, request: &R) -> Result<Vec<T>, QueryError>
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: IntoIterator<Item = T> + DeserializeOwned,
+        T: DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner.paginated_request(request))
+    }
+}
@@ -0,0 +1,229 @@
+//! optional HTTP response cache for `GET` requests
+//!
+//! Responses are stored together with their `ETag` and `Last-Modified` validators, which are sent
+//! back to the server as `If-None-Match` and `If-Modified-Since` headers when the same URL is
+//! requested again. If the server did not send any validators, cached responses are considered
+//! fresh until their time-to-live has expired.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_TTL: Duration = Duration::from_secs(3600);
+
+/// cached response body with its validators
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CacheEntry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// time when the entry was stored or last revalidated (seconds since the UNIX epoch)
+    pub stored: u64,
+}
+
+impl CacheEntry {
+    pub(crate) fn new(body: String, etag: Option<String>, last_modified: Option<String>) -> Self {
+        CacheEntry {
+            body,
+            etag,
+            last_modified,
+            stored: now(),
+        }
+    }
+
+    pub(crate) fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+
+    pub(crate) fn is_fresh(&self, ttl: Duration) -> bool {
+        now().saturating_sub(self.stored) < ttl.as_secs()
+    }
+
+    pub(crate) fn touch(&mut self) {
+        self.stored = now();
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// storage backend for cached responses
+///
+/// Cache keys are full request URLs.
+pub trait CacheStorage: Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+    fn put(&self, key: &str, entry: CacheEntry);
+    fn remove(&self, key: &str);
+}
+
+/// in-memory cache storage
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheStorage for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.lock().expect("Poisoned lock for cache.").get(key).cloned()
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        self.entries
+            .lock()
+            .expect("Poisoned lock for cache.")
+            .insert(key.to_string(), entry);
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().expect("Poisoned lock for cache.").remove(key);
+    }
+}
+
+/// on-disk cache storage, with one JSON file per cached URL
+#[derive(Debug)]
+pub struct DiskCache {
+    directory: PathBuf,
+}
+
+impl DiskCache {
+    pub fn new(directory: impl Into<PathBuf>) -> Result<Self, std::io::Error> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(DiskCache { directory })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{:016x}.json", fnv1a(key)))
+    }
+}
+
+impl CacheStorage for DiskCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let contents = fs::read_to_string(self.path(key)).ok()?;
+        let (stored_key, entry): (String, CacheEntry) = serde_json::from_str(&contents).ok()?;
+
+        // guard against hash collisions
+        if stored_key == key {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let result = serde_json::to_string(&(key, entry))
+            .map_err(std::io::Error::from)
+            .and_then(|contents| fs::write(self.path(key), contents));
+
+        if let Err(error) = result {
+            log::warn!("Failed to write cache entry for {}: {}", key, error);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+    }
+}
+
+/// 64-bit FNV-1a hash, used for computing stable file names for cache entries
+fn fnv1a(string: &str) -> u64 {
+    string.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// response cache configuration for [`ClientBuilder::with_cache`](crate::ClientBuilder::with_cache)
+#[derive(Clone, Debug)]
+pub struct Cache {
+    pub(crate) storage: Arc<dyn CacheStorage>,
+    pub(crate) ttl: Duration,
+}
+
+impl Cache {
+    pub fn new<S>(storage: S) -> Self
+    where
+        S: CacheStorage + 'static,
+    {
+        Cache {
+            storage: Arc::new(storage),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    pub fn memory() -> Self {
+        Self::new(MemoryCache::new())
+    }
+
+    pub fn disk(directory: impl Into<PathBuf>) -> Result<Self, std::io::Error> {
+        Ok(Self::new(DiskCache::new(directory)?))
+    }
+
+    /// time-to-live for cached responses without `ETag` or `Last-Modified` validators
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+/// per-request cache behaviour, set with
+/// [`AnityaClient::cache_mode`](crate::AnityaClient::cache_mode)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CacheMode {
+    /// serve fresh responses from the cache and revalidate stale ones
+    #[default]
+    Default,
+    /// neither read from nor write to the cache
+    Bypass,
+    /// ignore cached responses, but store new responses in the cache
+    Refresh,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disk_cache() {
+        let directory = std::env::temp_dir().join(format!("anitya-cache-{}", std::process::id()));
+        let cache = DiskCache::new(&directory).unwrap();
+
+        let key = "https://release-monitoring.org/api/v2/versions/?project_id=7635";
+        assert!(cache.get(key).is_none());
+
+        cache.put(
+            key,
+            CacheEntry::new(String::from("{}"), Some(String::from("\"abc\"")), None),
+        );
+        let entry = cache.get(key).unwrap();
+        assert_eq!(entry.body, "{}");
+        assert_eq!(entry.etag.as_deref(), Some("\"abc\""));
+        assert!(entry.is_fresh(Duration::from_secs(60)));
+        assert!(!entry.is_fresh(Duration::ZERO));
+
+        cache.remove(key);
+        assert!(cache.get(key).is_none());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(fnv1a(""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a("a"), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -0,0 +1,188 @@
+//! record / replay [`Transport`] implementations for deterministic offline tests
+//!
+//! In [`CassetteMode::Record`] mode, all requests are sent over the wrapped transport, and the
+//! request / response pairs are written to a JSON "cassette" file. In [`CassetteMode::Replay`]
+//! mode, responses are served from a previously recorded cassette file instead, and requests that
+//! are not found in the cassette result in an error.
+
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::QueryError;
+use crate::transport::{HttpRequest, HttpResponse, Transport};
+
+const REDACTED: &str = "(redacted)";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CassetteMode {
+    /// send requests over the network and record interactions
+    Record,
+    /// serve responses from previously recorded interactions
+    Replay,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CassetteError {
+    #[error("Failed to read or write cassette file: {}", error)]
+    Io {
+        #[from]
+        error: std::io::Error,
+    },
+    #[error("Failed to (de)serialize cassette: {}", error)]
+    Json {
+        #[from]
+        error: serde_json::Error,
+    },
+    #[error("No recorded interaction for request: {} {}", method, url)]
+    Unexpected { method: String, url: String },
+}
+
+impl From<CassetteError> for QueryError {
+    fn from(error: CassetteError) -> Self {
+        QueryError::Transport {
+            error: Box::new(error),
+            transient: false,
+        }
+    }
+}
+
+/// single recorded request / response pair
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Interaction {
+    pub request: HttpRequest,
+    pub response: HttpResponse,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    fn load(path: &Path) -> Result<Self, CassetteError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), CassetteError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// [`Transport`] wrapper that records all interactions to a cassette file
+///
+/// The cassette file is rewritten after every interaction, so it is complete even if the process
+/// is terminated early. Values of sensitive request headers (by default, only `Authorization`) are
+/// redacted before they are written to disk.
+#[derive(Debug)]
+pub struct RecordingTransport<T> {
+    inner: T,
+    path: PathBuf,
+    redacted: Vec<String>,
+    cassette: Mutex<Cassette>,
+}
+
+impl<T> RecordingTransport<T>
+where
+    T: Transport,
+{
+    pub fn new(inner: T, path: impl Into<PathBuf>) -> Self {
+        RecordingTransport {
+            inner,
+            path: path.into(),
+            redacted: vec![String::from("Authorization")],
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// redact the value of an additional request header in recordings
+    pub fn redact(mut self, header: &str) -> Self {
+        self.redacted.push(header.to_string());
+        self
+    }
+
+    fn record(&self, mut request: HttpRequest, response: &HttpResponse) -> Result<(), CassetteError> {
+        for (key, value) in request.headers.iter_mut() {
+            if self.redacted.iter().any(|header| header.eq_ignore_ascii_case(key)) {
+                *value = String::from(REDACTED);
+            }
+        }
+
+        let mut cassette = self.cassette.lock().expect("Poisoned lock for cassette.");
+        cassette.interactions.push(Interaction {
+            request,
+            response: response.clone(),
+        });
+        cassette.save(&self.path)
+    }
+}
+
+impl<T> Transport for RecordingTransport<T>
+where
+    T: Transport,
+{
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        Box::pin(async move {
+            let response = self.inner.send(request.clone()).await?;
+            self.record(request, &response)?;
+            Ok(response)
+        })
+    }
+}
+
+/// [`Transport`] implementation that serves responses from a cassette file
+///
+/// Requests are matched against recorded interactions by method, URL, and body. Every recorded
+/// interaction is only served once, in the order in which they were recorded.
+#[derive(Debug)]
+pub struct ReplayTransport {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl ReplayTransport {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let cassette = Cassette::load(path.as_ref())?;
+
+        Ok(ReplayTransport {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        })
+    }
+
+    fn replay(&self, request: &HttpRequest) -> Result<HttpResponse, CassetteError> {
+        let mut interactions = self.interactions.lock().expect("Poisoned lock for cassette.");
+
+        let slot = interactions.iter_mut().find(|slot| match slot {
+            Some(interaction) => {
+                interaction.request.method == request.method
+                    && interaction.request.url == request.url
+                    && interaction.request.body == request.body
+            },
+            None => false,
+        });
+
+        match slot.and_then(Option::take) {
+            Some(interaction) => Ok(interaction.response),
+            None => Err(CassetteError::Unexpected {
+                method: format!("{:?}", request.method),
+                url: request.url.to_string(),
+            }),
+        }
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        let result = self.replay(&request).map_err(QueryError::from);
+        Box::pin(async move { result })
+    }
+}
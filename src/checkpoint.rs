@@ -0,0 +1,93 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::QueryError;
+
+/// paginated request that can be resumed after a partial failure
+pub trait Resumable: Clone {
+    /// number of items per page
+    fn page_size(&self) -> u32;
+
+    /// continue with the page after `last_page`, with the given number of items per page
+    fn resume(self, last_page: u32, items_per_page: u32) -> Self;
+}
+
+/// serializable state of a paginated request that failed after some pages were fetched
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Checkpoint<Q> {
+    /// parameters of the paginated request
+    pub query: Q,
+    /// last page that was fetched successfully
+    pub last_page: u32,
+    pub items_per_page: u32,
+}
+
+impl<Q> Checkpoint<Q>
+where
+    Q: Resumable,
+{
+    /// construct a query that continues where the failed request stopped
+    pub fn resume(self) -> Q {
+        self.query.resume(self.last_page, self.items_per_page)
+    }
+}
+
+/// results of a paginated request that failed after some pages were fetched
+pub struct PartialResults<T, Q> {
+    /// items from all pages that were fetched successfully
+    pub items: Vec<T>,
+    /// checkpoint for resuming the request
+    pub checkpoint: Checkpoint<Q>,
+    /// error that caused the request to fail
+    pub error: QueryError,
+}
+
+impl<T, Q> Debug for PartialResults<T, Q>
+where
+    Q: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PartialResults")
+            .field("items", &self.items.len())
+            .field("checkpoint", &self.checkpoint)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<T, Q> Display for PartialResults<T, Q> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Paginated request failed after page {} ({} items fetched): {}",
+            self.checkpoint.last_page,
+            self.items.len(),
+            self.error
+        )
+    }
+}
+
+impl<T, Q> std::error::Error for PartialResults<T, Q>
+where
+    Q: Debug,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<T, Q> From<PartialResults<T, Q>> for QueryError {
+    fn from(partial: PartialResults<T, Q>) -> Self {
+        partial.error
+    }
+}
+
+/// allows using `?` on the result of
+/// [`AnityaClient::resumable_request`](crate::AnityaClient::resumable_request) when partial results
+/// are not needed
+impl<T, Q> From<Box<PartialResults<T, Q>>> for QueryError {
+    fn from(partial: Box<PartialResults<T, Q>>) -> Self {
+        partial.error
+    }
+}
@@ -1,18 +1,42 @@
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
-use reqwest::header::{HeaderMap, HeaderValue, InvalidHeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
-use reqwest::Client;
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use reqwest::header::{HeaderValue, InvalidHeaderValue};
 use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
 use url::Url;
 
+use crate::cache::{Cache, CacheEntry, CacheMode};
+use crate::cassette::{CassetteError, CassetteMode, RecordingTransport, ReplayTransport};
+use crate::checkpoint::{Checkpoint, PartialResults, Resumable};
+use crate::consistency::{ConsistencyMode, ConsistentResults, ItemKey};
 use crate::errors::QueryError;
+use crate::fields::StrictMode;
+use crate::progress::ProgressTracker;
+use crate::ratelimit::{RateLimit, RateLimiter};
 use crate::request::{PaginatedRequest, Pagination, RequestMethod, SingleRequest};
+use crate::retry::{retry_after, RetryPolicy};
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
+
+const USER_AGENT: &str = "anitya-rs";
 
 pub struct ClientBuilder<'a> {
     url: &'a str,
     token: Option<&'a str>,
     delay: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    max_concurrent_pages: Option<usize>,
+    rate_limit: Option<RateLimit>,
+    transport: Option<Arc<dyn Transport>>,
+    cassette: Option<(PathBuf, CassetteMode)>,
+    cache: Option<Cache>,
+    strict_mode: StrictMode,
+    validation: bool,
 }
 
 impl<'a> Debug for ClientBuilder<'a> {
@@ -24,6 +48,14 @@ impl<'a> Debug for ClientBuilder<'a> {
             .field("url", &self.url)
             .field("token", &token)
             .field("delay", &delay)
+            .field("retry", &self.retry)
+            .field("max_concurrent_pages", &self.max_concurrent_pages)
+            .field("rate_limit", &self.rate_limit)
+            .field("transport", &self.transport)
+            .field("cassette", &self.cassette)
+            .field("cache", &self.cache)
+            .field("strict_mode", &self.strict_mode)
+            .field("validation", &self.validation)
             .finish()
     }
 }
@@ -34,6 +66,14 @@ impl<'a> ClientBuilder<'a> {
             url,
             token: None,
             delay: None,
+            retry: None,
+            max_concurrent_pages: None,
+            rate_limit: None,
+            transport: None,
+            cassette: None,
+            cache: None,
+            strict_mode: StrictMode::Ignore,
+            validation: false,
         }
     }
 
@@ -47,23 +87,100 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// fetch up to `n` pages of paginated requests concurrently
+    ///
+    /// Once the first page of results has been fetched, the remaining pages are requested in
+    /// parallel. Results are still returned in page order, and requests are still spaced out by
+    /// the delay configured with [`ClientBuilder::with_delay`].
+    pub fn max_concurrent_pages(mut self, n: usize) -> Self {
+        self.max_concurrent_pages = Some(n.max(1));
+        self
+    }
+
+    /// limit the rate of all HTTP requests made by the client
+    ///
+    /// The rate limit is shared between all clones of the resulting [`AnityaClient`].
+    pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// use a custom HTTP stack instead of the default [`ReqwestTransport`]
+    pub fn with_transport<T>(mut self, transport: T) -> Self
+    where
+        T: Transport + 'static,
+    {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// record interactions to a cassette file, or replay previously recorded interactions
+    ///
+    /// When recording, requests are sent with the transport that was set with
+    /// [`ClientBuilder::with_transport`] (if any). Replaying cannot be combined with a custom
+    /// transport, and building such a client fails.
+    ///
+    /// See the [`cassette`](crate::cassette) module for details.
+    pub fn with_cassette(mut self, path: impl Into<PathBuf>, mode: CassetteMode) -> Self {
+        self.cassette = Some((path.into(), mode));
+        self
+    }
+
+    /// cache responses to `GET` requests
+    ///
+    /// See the [`cache`](crate::cache) module for details.
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// report fields in responses that are not known to this crate
+    ///
+    /// See [`StrictMode`] for details.
+    pub fn with_strict_mode(mut self, strict_mode: StrictMode) -> Self {
+        self.strict_mode = strict_mode;
+        self
+    }
+
+    /// validate requests with [`SingleRequest::validate`] before they are sent
+    ///
+    /// Requests with problems are rejected with [`QueryError::InvalidRequest`] without sending
+    /// them to the server.
+    pub fn with_validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+
     pub fn build(self) -> Result<AnityaClient, ClientBuildError> {
-        let url = Url::parse(self.url)?;
-        let user_agent = "anitya-rs";
+        let mut url = Url::parse(self.url)?;
 
-        let mut headers = HeaderMap::new();
-        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-        headers.insert(USER_AGENT, HeaderValue::from_static(user_agent));
+        // the base URL must end with a slash, otherwise its last path segment is dropped when
+        // request paths are joined with it
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
 
-        let session = reqwest::ClientBuilder::new()
-            .default_headers(headers)
-            //.timeout()
-            .build()?;
+        let transport: Arc<dyn Transport> = match (self.transport, self.cassette) {
+            (Some(_), Some((_, CassetteMode::Replay))) => return Err(ClientBuildError::ReplayWithTransport),
+            (None, Some((path, CassetteMode::Replay))) => Arc::new(ReplayTransport::new(path)?),
+            (Some(transport), Some((path, CassetteMode::Record))) => Arc::new(RecordingTransport::new(transport, path)),
+            (None, Some((path, CassetteMode::Record))) => {
+                Arc::new(RecordingTransport::new(ReqwestTransport::new()?, path))
+            },
+            (Some(transport), None) => transport,
+            (None, None) => Arc::new(ReqwestTransport::new()?),
+        };
 
         let auth_header = if let Some(token) = self.token {
-            let mut value = HeaderValue::from_str(&format!("token {token}"))?;
-            value.set_sensitive(true);
+            let value = format!("token {token}");
+            // validate that the token can be sent as an HTTP header value
+            HeaderValue::from_str(&value)?;
             Some(value)
         } else {
             None
@@ -71,11 +188,24 @@ impl<'a> ClientBuilder<'a> {
 
         Ok(AnityaClient {
             url,
-            session,
+            transport,
             auth_header,
             delay: self.delay,
+            retry: self.retry,
+            max_concurrent_pages: self.max_concurrent_pages,
+            pacer: self.delay.map(|delay| Arc::new(Pacer::new(delay))),
+            rate_limiter: self.rate_limit.map(|limit| Arc::new(RateLimiter::new(limit))),
+            cache: self.cache,
+            cache_mode: CacheMode::Default,
+            strict_mode: self.strict_mode,
+            validation: self.validation,
         })
     }
+
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::AnityaClient, ClientBuildError> {
+        crate::blocking::AnityaClient::new(self.build()?)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -95,13 +225,35 @@ pub enum ClientBuildError {
         #[from]
         error: reqwest::Error,
     },
+    #[error("Failed to load cassette: {}", error)]
+    Cassette {
+        #[from]
+        error: CassetteError,
+    },
+    #[error("Failed to build client: a custom transport cannot be used while replaying a cassette")]
+    ReplayWithTransport,
+    #[cfg(feature = "blocking")]
+    #[error("Failed to initialize async runtime: {}", error)]
+    Runtime {
+        #[from]
+        error: std::io::Error,
+    },
 }
 
+#[derive(Clone)]
 pub struct AnityaClient {
     url: Url,
-    session: Client,
-    auth_header: Option<HeaderValue>,
+    transport: Arc<dyn Transport>,
+    auth_header: Option<String>,
     delay: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    max_concurrent_pages: Option<usize>,
+    pacer: Option<Arc<Pacer>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Cache>,
+    cache_mode: CacheMode,
+    strict_mode: StrictMode,
+    validation: bool,
 }
 
 impl Debug for AnityaClient {
@@ -111,106 +263,360 @@ impl Debug for AnityaClient {
 
         f.debug_struct("AnityaClient")
             .field("url", &self.url)
-            .field("session", &self.session)
+            .field("transport", &self.transport)
             .field("auth_header", &auth_header)
             .field("delay", &delay)
+            .field("retry", &self.retry)
+            .field("max_concurrent_pages", &self.max_concurrent_pages)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("cache", &self.cache)
+            .field("cache_mode", &self.cache_mode)
+            .field("strict_mode", &self.strict_mode)
+            .field("validation", &self.validation)
             .finish()
     }
 }
 
 impl AnityaClient {
+    /// get a copy of this client that uses a different [`CacheMode`] for its requests
+    ///
+    /// This can be used to bypass the cache or to force a refresh for individual requests, for
+    /// example with `client.cache_mode(CacheMode::Refresh).request(&query)`.
+    pub fn cache_mode(&self, cache_mode: CacheMode) -> Self {
+        AnityaClient {
+            cache_mode,
+            ..self.clone()
+        }
+    }
+
     pub async fn request<R, P, T>(&self, request: &R) -> Result<T, QueryError>
     where
         R: SingleRequest<P, T>,
         T: DeserializeOwned,
     {
-        let url = self.url.join(&request.path()?)?;
+        let string = self.send(request).await?;
 
-        let response = match request.method() {
-            RequestMethod::GET => match request.body()? {
-                Some(body) => self.session.get(url).body(body).send().await?,
-                None => self.session.get(url).send().await?,
-            },
-            RequestMethod::POST => {
-                let auth_header = if let Some(ref token) = self.auth_header {
-                    token
-                } else {
-                    return Err(QueryError::Unauthorized);
-                };
-
-                let mut headers = HeaderMap::new();
-                headers.insert(AUTHORIZATION, auth_header.clone());
+        let page = request.parse(&string)?;
+        self.check_fields(request, &page)?;
 
-                match request.body()? {
-                    Some(body) => {
-                        let request = self.session.post(url).headers(headers).body(body).build()?;
-                        self.session.execute(request).await?
-                    },
-                    None => {
-                        let request = self.session.post(url).headers(headers).build()?;
-                        self.session.execute(request).await?
-                    },
-                }
-            },
-        };
+        Ok(request.extract(page))
+    }
 
-        let status = response.status();
+    pub async fn paginated_request<R, S, P, V, T>(&self, request: &R) -> Result<Vec<T>, QueryError>
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: IntoIterator<Item = T> + DeserializeOwned,
+        T: DeserializeOwned,
+    {
+        self.paginate(request).await.map_err(|(_, _, error)| error)
+    }
 
-        if status.is_success() {
-            let string = response.text().await?;
-            let page = request.parse(&string)?;
-            Ok(request.extract(page))
-        } else {
-            Err(QueryError::Request {
-                code: status.as_u16(),
-                error: response.text().await?,
+    /// fetch the results of a paginated request, keeping partial results in case of failure
+    ///
+    /// If the request fails after some pages have been fetched, the items from these pages are
+    /// returned together with a [`Checkpoint`], which can be serialized and used to resume the
+    /// request later (with [`Checkpoint::resume`]).
+    pub async fn resumable_request<R, S, P, V, T>(&self, request: &R) -> Result<Vec<T>, Box<PartialResults<T, R>>>
+    where
+        R: PaginatedRequest<P, V, S> + Resumable,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: IntoIterator<Item = T> + DeserializeOwned,
+        T: DeserializeOwned,
+    {
+        self.paginate(request).await.map_err(|(items, last_page, error)| {
+            Box::new(PartialResults {
+                items,
+                checkpoint: Checkpoint {
+                    query: request.clone(),
+                    last_page,
+                    items_per_page: request.page_size(),
+                },
+                error,
             })
-        }
+        })
     }
 
-    pub async fn paginated_request<'a, R, S, P, V, T>(&self, request: &'a R) -> Result<Vec<T>, QueryError>
+    /// fetch all pages of a paginated request
+    ///
+    /// In case of failure, the items that were fetched successfully are returned together with
+    /// the number of the last page that was fetched successfully.
+    async fn paginate<R, S, P, V, T>(&self, request: &R) -> Result<Vec<T>, (Vec<T>, u32, QueryError)>
     where
-        R: PaginatedRequest<'a, P, V, S>,
-        S: SingleRequest<P, V> + 'a,
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
         P: Pagination,
         V: IntoIterator<Item = T> + DeserializeOwned,
         T: DeserializeOwned,
     {
         let mut results: Vec<T> = Vec::new();
 
+        let first_page = request.first_page();
+        let mut tracker = ProgressTracker::new(self.delay, first_page.saturating_sub(1));
+
         // initialize progress callback with "zero progress"
-        request.callback(0, 1);
+        request.report_progress(tracker.progress());
 
-        let first_request = request.page_request(1);
-        let first_page = self.page_request(&first_request).await?;
+        let first_request = request.page_request(first_page);
+        let first_response = match self.page_request(&first_request).await {
+            Ok(response) => response,
+            Err(error) => return Err((results, first_page.saturating_sub(1), error)),
+        };
 
-        let mut page = 2u32;
-        let mut pages = first_page.pages();
+        let mut page = first_page + 1;
+        let mut pages = first_response.pages();
 
-        // update progress callback with actual total pages
-        request.callback(1, pages);
+        results.extend(first_request.extract(first_response));
 
-        results.extend(first_request.extract(first_page));
+        // update progress callback with actual total pages
+        request.report_progress(tracker.page_done(results.len(), pages));
+
+        if let Some(max_concurrent_pages) = self.max_concurrent_pages {
+            // the number of pages is fixed once concurrent requests have been started
+            let mut next_pages = stream::iter(page..=pages)
+                .map(|page| async move {
+                    if let Some(ref pacer) = self.pacer {
+                        pacer.wait().await;
+                    }
+
+                    let page_request = request.page_request(page);
+                    let next_page = self.page_request(&page_request).await?;
+                    Ok::<_, QueryError>(page_request.extract(next_page))
+                })
+                .buffered(max_concurrent_pages);
+
+            // pages are returned in order, so all previous pages are complete if a page fails
+            loop {
+                match next_pages.try_next().await {
+                    Ok(Some(items)) => {
+                        let before = results.len();
+                        results.extend(items);
+
+                        request.report_progress(tracker.page_done(results.len() - before, pages));
+                        page += 1;
+                    },
+                    Ok(None) => return Ok(results),
+                    Err(error) => return Err((results, page - 1, error)),
+                }
+            }
+        }
 
         while page <= pages {
             let page_request = request.page_request(page);
-            let next_page = self.page_request(&page_request).await?;
-
-            request.callback(page, pages);
+            let next_page = match self.page_request(&page_request).await {
+                Ok(next_page) => next_page,
+                Err(error) => return Err((results, page - 1, error)),
+            };
 
             page += 1;
             pages = next_page.pages();
 
+            let before = results.len();
             results.extend(page_request.extract(next_page));
 
+            request.report_progress(tracker.page_done(results.len() - before, pages));
+
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// fetch a single page of the results of a paginated request
+    pub async fn page<R, S, P, V>(&self, request: &R, page: u32) -> Result<P, QueryError>
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: DeserializeOwned,
+    {
+        self.page_request(&request.page_request(page)).await
+    }
+
+    /// determine the total number of results of a paginated request without fetching all pages
+    pub async fn count<R, S, P, V>(&self, request: &R) -> Result<u32, QueryError>
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: DeserializeOwned,
+    {
+        let page = self.page_request(&request.count_request()).await?;
+        Ok(page.total_items())
+    }
+
+    /// fetch the results of a paginated request, with checks for changes of the dataset
+    ///
+    /// Items are de-duplicated, and changes of the total number of items between pages are
+    /// detected and handled according to the [`ConsistencyMode`]. Pages are always fetched one
+    /// after another for this kind of request.
+    pub async fn consistent_request<R, S, P, V, T>(
+        &self,
+        request: &R,
+        mode: ConsistencyMode,
+    ) -> Result<ConsistentResults<T>, QueryError>
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V>,
+        P: Pagination,
+        V: IntoIterator<Item = T> + DeserializeOwned,
+        T: DeserializeOwned + ItemKey,
+    {
+        let mut results = ConsistentResults {
+            items: Vec::new(),
+            duplicates: 0,
+            drift: 0,
+            possibly_missed: 0,
+        };
+        let mut seen = HashSet::new();
+
+        // add items from a page, skipping items that have already been seen, and return the
+        // number of items that were new
+        let mut add = |results: &mut ConsistentResults<T>, items: V| {
+            let mut added = 0;
+            for item in items {
+                if seen.insert(item.key()) {
+                    results.items.push(item);
+                    added += 1;
+                } else {
+                    results.duplicates += 1;
+                }
+            }
+            added
+        };
+
+        let first_page = request.first_page();
+        let mut tracker = ProgressTracker::new(self.delay, first_page.saturating_sub(1));
+
+        // initialize progress callback with "zero progress"
+        request.report_progress(tracker.progress());
+
+        let first_request = request.page_request(first_page);
+        let first_response = self.page_request(&first_request).await?;
+
+        let initial_total = first_response.total_items();
+        let mut total = initial_total;
+        let mut pages = first_response.pages();
+
+        let added = add(&mut results, first_request.extract(first_response));
+        request.report_progress(tracker.page_done(added, pages));
+
+        // all items on the first page are new, and the first page is full if there are more pages
+        let page_size = u32::try_from(added).unwrap_or(u32::MAX).max(1);
+
+        let mut page = first_page + 1;
+
+        while page <= pages {
             if let Some(delay) = self.delay {
                 tokio::time::sleep(delay).await;
             }
+
+            let page_request = request.page_request(page);
+            let next_page = self.page_request(&page_request).await?;
+
+            let new_total = next_page.total_items();
+
+            if new_total != total {
+                log::warn!(
+                    "Total number of items changed from {} to {} while fetching page {}",
+                    total,
+                    new_total,
+                    page
+                );
+
+                // removed items cause remaining items to move to pages that were already fetched
+                if new_total < total {
+                    let removed = total - new_total;
+
+                    match mode {
+                        ConsistencyMode::Report => {
+                            results.possibly_missed += removed;
+                        },
+                        ConsistencyMode::Refetch => {
+                            // removed items can shift remaining items back by more than one page
+                            let distance = ((removed + page_size - 1) / page_size).min(page - first_page);
+
+                            let mut recovered = 0;
+                            for previous in (page - distance..page).rev() {
+                                let previous_request = request.page_request(previous);
+                                let previous_page = self.page_request(&previous_request).await?;
+                                recovered += add(&mut results, previous_request.extract(previous_page));
+                            }
+
+                            results.possibly_missed += removed.saturating_sub(recovered as u32);
+                        },
+                    }
+                }
+
+                total = new_total;
+            }
+
+            pages = next_page.pages();
+
+            let added = add(&mut results, page_request.extract(next_page));
+            request.report_progress(tracker.page_done(added, pages));
+
+            page += 1;
         }
 
+        results.drift = i64::from(total) - i64::from(initial_total);
+
         Ok(results)
     }
 
+    /// lazily fetch the results of a paginated request as a [`Stream`]
+    ///
+    /// In contrast to [`AnityaClient::paginated_request`], pages are only requested from the
+    /// server when the items from the previous page have been consumed, and no further requests
+    /// are made once the stream is dropped.
+    pub fn paginated_stream<'a, R, S, P, V, T>(
+        &'a self,
+        request: &'a R,
+    ) -> impl Stream<Item = Result<T, QueryError>> + 'a
+    where
+        R: PaginatedRequest<P, V, S>,
+        S: SingleRequest<P, V> + 'a,
+        P: Pagination,
+        V: IntoIterator<Item = T> + DeserializeOwned,
+        T: DeserializeOwned + 'a,
+    {
+        // (next page, total pages, progress)
+        let state: (u32, Option<u32>, Option<ProgressTracker>) = (request.first_page(), None, None);
+
+        stream::try_unfold(state, move |(page, pages, tracker)| async move {
+            let mut tracker = match (pages, tracker) {
+                (Some(pages), _) if page > pages => return Ok::<_, QueryError>(None),
+                (_, Some(tracker)) => {
+                    if let Some(delay) = self.delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    tracker
+                },
+                (_, None) => {
+                    // initialize progress callback with "zero progress"
+                    let tracker = ProgressTracker::new(self.delay, page.saturating_sub(1));
+                    request.report_progress(tracker.progress());
+                    tracker
+                },
+            };
+
+            let page_request = request.page_request(page);
+            let next_page = self.page_request(&page_request).await?;
+            let pages = next_page.pages();
+
+            let items: Vec<T> = page_request.extract(next_page).into_iter().collect();
+            request.report_progress(tracker.page_done(items.len(), pages));
+
+            Ok(Some((items, (page + 1, Some(pages), Some(tracker)))))
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten()
+    }
+
     async fn page_request<R, P, T>(&self, request: &R) -> Result<P, QueryError>
     where
         R: SingleRequest<P, T>,
@@ -219,24 +625,208 @@ impl AnityaClient {
     {
         debug_assert!(request.method() == RequestMethod::GET);
 
-        let url = self.url.join(&request.path()?)?;
+        let string = self.send(request).await?;
+
+        let page = request.parse(&string)?;
+        self.check_fields(request, &page)?;
+
+        Ok(page)
+    }
+
+    /// handle fields in a parsed response that are not known to this crate
+    fn check_fields<R, P, T>(&self, request: &R, page: &P) -> Result<(), QueryError>
+    where
+        R: SingleRequest<P, T>,
+        T: DeserializeOwned,
+    {
+        if self.strict_mode == StrictMode::Ignore {
+            return Ok(());
+        }
+
+        let fields = request.unknown_fields(page);
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        match self.strict_mode {
+            StrictMode::Ignore => Ok(()),
+            StrictMode::Warn => {
+                log::warn!("Response contained unknown fields: {}", fields.join(", "));
+                Ok(())
+            },
+            StrictMode::Error => Err(QueryError::UnknownFields { fields }),
+        }
+    }
 
-        let response = match request.body()? {
-            Some(body) => self.session.get(url).body(body).send().await?,
-            None => self.session.get(url).send().await?,
+    /// compute the full URL for a request path, relative to the base URL of the anitya instance
+    ///
+    /// Request paths are absolute (i.e. `/api/v2/...`), but they are resolved relative to the base
+    /// URL, so that instances that are deployed under a sub-path are supported.
+    fn request_url(&self, path: &str) -> Result<Url, QueryError> {
+        Ok(self.url.join(path.trim_start_matches('/'))?)
+    }
+
+    async fn send<R, P, T>(&self, request: &R) -> Result<String, QueryError>
+    where
+        R: SingleRequest<P, T>,
+        T: DeserializeOwned,
+    {
+        let method = request.method();
+
+        if self.validation {
+            request
+                .validate()
+                .map_err(|diagnostics| QueryError::InvalidRequest { diagnostics })?;
+        }
+
+        let url = self.request_url(&request.path()?)?;
+        let body = request.body()?;
+
+        let mut headers = vec![
+            (String::from("Accept"), String::from("application/json")),
+            (String::from("Content-Type"), String::from(request.content_type())),
+            (String::from("User-Agent"), String::from(USER_AGENT)),
+        ];
+
+        if request.authenticated() {
+            let auth_header = if let Some(ref token) = self.auth_header {
+                token
+            } else {
+                return Err(QueryError::Unauthorized);
+            };
+
+            headers.push((String::from("Authorization"), auth_header.clone()));
+        }
+
+        let mut request = HttpRequest {
+            method,
+            url,
+            headers,
+            body,
+        };
+
+        let cache = match self.cache {
+            Some(ref cache) if method == RequestMethod::GET && self.cache_mode != CacheMode::Bypass => cache,
+            _ => return Ok(self.execute(request).await?.body),
         };
 
-        let status = response.status();
+        let key = request.url.to_string();
 
-        if status.is_success() {
-            let string = response.text().await?;
-            let page = request.parse(&string)?;
-            Ok(page)
-        } else {
-            Err(QueryError::Request {
-                code: status.as_u16(),
-                error: response.text().await?,
-            })
+        let cached = match self.cache_mode {
+            CacheMode::Default => cache.storage.get(&key),
+            _ => None,
+        };
+
+        if let Some(ref entry) = cached {
+            if !entry.has_validators() && entry.is_fresh(cache.ttl) {
+                log::debug!("Serving response for {} from cache", key);
+                return Ok(entry.body.clone());
+            }
+
+            if let Some(ref etag) = entry.etag {
+                request.headers.push((String::from("If-None-Match"), etag.clone()));
+            }
+            if let Some(ref last_modified) = entry.last_modified {
+                request
+                    .headers
+                    .push((String::from("If-Modified-Since"), last_modified.clone()));
+            }
         }
+
+        let response = self.execute(request).await?;
+
+        match cached {
+            Some(mut entry) if response.status == 304 => {
+                log::debug!("Cached response for {} is still valid", key);
+                entry.touch();
+                cache.storage.put(&key, entry.clone());
+                Ok(entry.body)
+            },
+            _ => {
+                let etag = response.header("ETag").map(String::from);
+                let last_modified = response.header("Last-Modified").map(String::from);
+
+                let entry = CacheEntry::new(response.body, etag, last_modified);
+                cache.storage.put(&key, entry.clone());
+                Ok(entry.body)
+            },
+        }
+    }
+
+    /// send a request, retrying it according to the configured [`RetryPolicy`]
+    ///
+    /// Successful responses and `304 Not Modified` responses are returned, all other responses
+    /// are turned into errors.
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse, QueryError> {
+        let method = request.method;
+        let mut attempt = 1u32;
+
+        loop {
+            if let Some(ref rate_limiter) = self.rate_limiter {
+                rate_limiter.acquire().await;
+            }
+
+            let retry = self.retry.as_ref().filter(|retry| retry.allows(&method, attempt));
+
+            let response = match self.transport.send(request.clone()).await {
+                Ok(response) => response,
+                Err(error) => match retry {
+                    Some(retry) if error.is_transient() => {
+                        let wait = retry.backoff(attempt);
+                        log::warn!("Request to {} failed ({}), retrying in {:?}", request.url, error, wait);
+                        tokio::time::sleep(wait).await;
+                        attempt += 1;
+                        continue;
+                    },
+                    _ => return Err(error),
+                },
+            };
+
+            let status = response.status;
+
+            if (200..300).contains(&status) || status == 304 {
+                return Ok(response);
+            }
+
+            let wait = retry
+                .filter(|retry| retry.is_retryable_status(status))
+                .and_then(|retry| retry.delay(retry_after(response.header("Retry-After")), attempt));
+
+            match wait {
+                Some(wait) => {
+                    log::warn!(
+                        "Request to {} failed (HTTP {}), retrying in {:?}",
+                        request.url,
+                        status,
+                        wait
+                    );
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                },
+                None => return Err(QueryError::from_response(method, request.url, response)),
+            }
+        }
+    }
+}
+
+/// helper for spacing out the start of concurrent requests by a fixed delay
+struct Pacer {
+    delay: Duration,
+    next: Mutex<Instant>,
+}
+
+impl Pacer {
+    fn new(delay: Duration) -> Self {
+        Pacer {
+            delay,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    async fn wait(&self) {
+        // the lock is held while waiting, so waiting requests are released in order
+        let mut next = self.next.lock().await;
+        tokio::time::sleep_until(*next).await;
+        *next = Instant::now() + self.delay;
     }
 }
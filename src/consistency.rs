@@ -0,0 +1,40 @@
+use std::hash::Hash;
+
+/// item of a paginated request that can be uniquely identified
+pub trait ItemKey {
+    type Key: Eq + Hash;
+
+    fn key(&self) -> Self::Key;
+}
+
+/// strategy for dealing with changes of the dataset during a paginated request
+///
+/// When items are added to or removed from the dataset while a paginated request is running,
+/// items can move from one page to another. Items that move to a page that has not been fetched
+/// yet are returned twice, and items that move to a page that has already been fetched are
+/// missed.
+///
+/// Duplicate items are always removed, and changes of the total number of items are always
+/// detected. This setting only controls how potentially missed items are handled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConsistencyMode {
+    /// only report the number of items that might have been missed
+    Report,
+    /// fetch previous pages again when items might have moved to them
+    ///
+    /// When `n` items were removed, the last `ceil(n / items_per_page)` pages are fetched again.
+    Refetch,
+}
+
+/// results of a paginated request with consistency checks
+#[derive(Debug)]
+pub struct ConsistentResults<T> {
+    /// de-duplicated items
+    pub items: Vec<T>,
+    /// number of duplicate items that were removed
+    pub duplicates: usize,
+    /// change of the total number of items between the first and the last page
+    pub drift: i64,
+    /// upper bound for the number of items that might have been missed
+    pub possibly_missed: u32,
+}
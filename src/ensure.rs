@@ -0,0 +1,233 @@
+use std::fmt::Display;
+
+use crate::client::AnityaClient;
+use crate::errors::QueryError;
+use crate::types::{Backend, Ecosystem};
+use crate::v2::{NewPackageRequest, NewProjectRequest, Package, PackageQuery, Project, ProjectQuery};
+
+/// result of an idempotent "create or look up" operation
+#[derive(Clone, Debug)]
+pub struct Ensured<T> {
+    /// created or existing entity
+    ///
+    /// For newly created entities, this is constructed from the response to the creation request.
+    pub value: T,
+    /// whether the entity was created by this operation
+    pub created: bool,
+    /// settings of an existing entity that differ from the requested ones
+    pub differences: Vec<Difference>,
+}
+
+/// setting of an existing entity that differs from the requested one
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Difference {
+    pub field: &'static str,
+    pub requested: Option<String>,
+    pub existing: Option<String>,
+}
+
+/// utility function for comparing a requested setting with the existing one
+fn compare<T>(differences: &mut Vec<Difference>, field: &'static str, requested: Option<&T>, existing: Option<&T>)
+where
+    T: Display + PartialEq + ?Sized,
+{
+    if requested != existing {
+        differences.push(Difference {
+            field,
+            requested: requested.map(ToString::to_string),
+            existing: existing.map(ToString::to_string),
+        });
+    }
+}
+
+/// ecosystem that anitya assigns to new projects with the given backend
+///
+/// Projects with backends that do not correspond to a package ecosystem use their homepage
+/// as ecosystem instead.
+fn ecosystem_for(backend: &Backend, homepage: &str) -> Ecosystem {
+    match backend {
+        Backend::CratesIo => Ecosystem::CratesIo,
+        Backend::MavenCentral => Ecosystem::Maven,
+        Backend::Npmjs => Ecosystem::Npm,
+        Backend::PyPI => Ecosystem::PyPI,
+        Backend::Rubygems => Ecosystem::Rubygems,
+        _ => Ecosystem::from(homepage),
+    }
+}
+
+/// idempotent operations for creating projects and packages
+impl AnityaClient {
+    /// create a project unless it already exists, and return the created or existing project
+    ///
+    /// Existing projects are looked up by their name. A project with that name is considered to
+    /// be the requested one if it is in the ecosystem that anitya would assign to the requested
+    /// project, if it has the requested homepage, or if it has the requested backend and version
+    /// URL (in this order of preference). Differences of the ecosystem, homepage, backend,
+    /// version URL, and regex of an existing project are reported, but the project is not
+    /// modified.
+    pub async fn ensure_project(&self, request: &NewProjectRequest) -> Result<Ensured<Project>, QueryError> {
+        let ecosystem = ecosystem_for(&request.backend, &request.homepage);
+
+        if let Some(project) = self.find_project(request, &ecosystem).await? {
+            return Ok(existing_project(request, &ecosystem, project));
+        }
+
+        match self.request(request).await {
+            Ok(new) => Ok(Ensured {
+                value: Project {
+                    backend: new.backend,
+                    created_on: new.created_on,
+                    ecosystem,
+                    homepage: new.homepage,
+                    id: new.id,
+                    name: new.name,
+                    regex: new.regex,
+                    updated_on: new.updated_on,
+                    version: new.version,
+                    version_url: new.version_url,
+                    versions: new.versions,
+                    stable_versions: new.stable_versions,
+                    extra: new.extra,
+                },
+                created: true,
+                differences: Vec::new(),
+            }),
+            // the project was created concurrently
+            Err(QueryError::Conflict { response }) => match self.find_project(request, &ecosystem).await? {
+                Some(project) => Ok(existing_project(request, &ecosystem, project)),
+                None => Err(QueryError::Conflict { response }),
+            },
+            Err(error) => Err(error),
+        }
+    }
+
+    /// create a package mapping unless it already exists, and return the created or existing
+    /// package
+    ///
+    /// Existing packages are looked up by their distribution and name. If an existing package
+    /// is mapped to a different project than the requested one, this is reported, but the
+    /// mapping is not modified.
+    pub async fn ensure_package(&self, request: &NewPackageRequest) -> Result<Ensured<Package>, QueryError> {
+        if let Some(package) = self.find_package(&request.distribution, &request.package_name).await? {
+            return Ok(existing_package(request, package));
+        }
+
+        match self.request(request).await {
+            Ok(new) => Ok(Ensured {
+                value: Package {
+                    distribution: new.distribution,
+                    name: new.name,
+                    project: request.project_name.clone(),
+                    ecosystem: request.project_ecosystem.clone(),
+                    version: None,
+                    extra: new.extra,
+                },
+                created: true,
+                differences: Vec::new(),
+            }),
+            // the package was created concurrently
+            Err(QueryError::Conflict { response }) => {
+                match self.find_package(&request.distribution, &request.package_name).await? {
+                    Some(package) => Ok(existing_package(request, package)),
+                    None => Err(QueryError::Conflict { response }),
+                }
+            },
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn find_project(
+        &self,
+        request: &NewProjectRequest,
+        ecosystem: &Ecosystem,
+    ) -> Result<Option<Project>, QueryError> {
+        let query = ProjectQuery::new().name(request.name.clone());
+        let mut candidates = self.paginated_request(&query).await?;
+
+        let same_ecosystem = |project: &Project| &project.ecosystem == ecosystem;
+        let same_homepage = |project: &Project| project.homepage == request.homepage;
+        let same_upstream = |project: &Project| {
+            project.backend == request.backend
+                && project.version_url.is_some()
+                && project.version_url == request.version_url
+        };
+
+        let position = candidates
+            .iter()
+            .position(same_ecosystem)
+            .or_else(|| candidates.iter().position(same_homepage))
+            .or_else(|| candidates.iter().position(same_upstream));
+
+        Ok(position.map(|index| candidates.swap_remove(index)))
+    }
+
+    async fn find_package(&self, distribution: &str, name: &str) -> Result<Option<Package>, QueryError> {
+        let query = PackageQuery::new()
+            .distribution(distribution.to_string())
+            .name(name.to_string())
+            .items_per_page(1);
+
+        let page = self.page(&query, 1).await?;
+        Ok(page.items.into_iter().next())
+    }
+}
+
+fn existing_project(request: &NewProjectRequest, ecosystem: &Ecosystem, project: Project) -> Ensured<Project> {
+    let mut differences = Vec::new();
+
+    compare(&mut differences, "ecosystem", Some(ecosystem), Some(&project.ecosystem));
+
+    compare(
+        &mut differences,
+        "homepage",
+        Some(request.homepage.as_str()),
+        Some(project.homepage.as_str()),
+    );
+    compare(
+        &mut differences,
+        "backend",
+        Some(&request.backend),
+        Some(&project.backend),
+    );
+    compare(
+        &mut differences,
+        "version_url",
+        request.version_url.as_deref(),
+        project.version_url.as_deref(),
+    );
+    compare(
+        &mut differences,
+        "regex",
+        request.regex.as_deref(),
+        project.regex.as_deref(),
+    );
+
+    Ensured {
+        value: project,
+        created: false,
+        differences,
+    }
+}
+
+fn existing_package(request: &NewPackageRequest, package: Package) -> Ensured<Package> {
+    let mut differences = Vec::new();
+
+    compare(
+        &mut differences,
+        "project_name",
+        Some(request.project_name.as_str()),
+        Some(package.project.as_str()),
+    );
+    compare(
+        &mut differences,
+        "project_ecosystem",
+        Some(&request.project_ecosystem),
+        Some(&package.ecosystem),
+    );
+
+    Ensured {
+        value: package,
+        created: false,
+        differences,
+    }
+}
@@ -1,3 +1,15 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use serde_json::Value;
+use url::Url;
+
+use crate::request::RequestMethod;
+use crate::retry::retry_after;
+use crate::transport::HttpResponse;
+use crate::validation::Diagnostic;
+
 #[derive(Debug, thiserror::Error)]
 pub enum QueryError {
     /// failure to (de)serialize a JSON value
@@ -28,10 +40,266 @@ pub enum QueryError {
         #[from]
         error: reqwest::Error,
     },
-    /// server returned an error response to a request
-    #[error("Server request resulted in an error: HTTP {} / {}", code, error)]
-    Request { code: u16, error: String },
+    /// request failed in a custom [`Transport`](crate::transport::Transport) implementation
+    #[error("Failed to send request: {}", error)]
+    Transport {
+        /// error returned by the transport
+        error: Box<dyn std::error::Error + Send + Sync>,
+        /// whether the failure is (potentially) transient, so the request can be retried
+        transient: bool,
+    },
+    /// server rejected a request because the requested entity does not exist (HTTP 404)
+    #[error("Not found: {}", response)]
+    NotFound { response: Box<ErrorResponse> },
+    /// server rejected a request because the entity already exists (HTTP 409)
+    #[error("Conflict: {}", response)]
+    Conflict { response: Box<ErrorResponse> },
+    /// server rejected a request because of invalid parameters (HTTP 400 / HTTP 422)
+    #[error("Invalid request: {}", response)]
+    Validation {
+        response: Box<ErrorResponse>,
+        /// error messages for individual fields, if the server reported any
+        errors: BTreeMap<String, Vec<String>>,
+    },
+    /// server rejected a request due to rate limiting (HTTP 429)
+    #[error("Rate limited: {}", response)]
+    RateLimited {
+        response: Box<ErrorResponse>,
+        /// delay requested by the server with a `Retry-After` header
+        retry_after: Option<Duration>,
+    },
+    /// server failed to process a request (HTTP 5xx)
+    #[error("Server error: {}", response)]
+    ServerError { response: Box<ErrorResponse> },
+    /// server returned any other error response to a request
+    #[error("Server request resulted in an error: {}", response)]
+    Request { response: Box<ErrorResponse> },
     /// failure caused by an attempt to call an authenticated API without a token
     #[error("Unauthorized request: no API token supplied")]
     Unauthorized,
+    /// response contained fields that are not known to this crate (only in strict mode)
+    #[error("Response contained unknown fields: {}", fields.join(", "))]
+    UnknownFields { fields: Vec<String> },
+    /// response lacks a field that is required for converting it into the API v2 model
+    #[error("Response is missing required field: {field}")]
+    MissingField { field: &'static str },
+    /// request was rejected by client-side validation
+    #[error(
+        "Invalid request: {}",
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    InvalidRequest { diagnostics: Vec<Diagnostic> },
+}
+
+impl QueryError {
+    /// construct an error from an unsuccessful HTTP response
+    pub(crate) fn from_response(method: RequestMethod, url: Url, response: HttpResponse) -> Self {
+        let retry_after = retry_after(response.header("Retry-After"));
+        let (message, errors) = parse_error_body(&response.body);
+
+        let status = response.status;
+        let response = Box::new(ErrorResponse {
+            method,
+            url,
+            status,
+            body: response.body,
+            message,
+        });
+
+        match status {
+            400 | 422 => QueryError::Validation { response, errors },
+            404 => QueryError::NotFound { response },
+            409 => QueryError::Conflict { response },
+            429 => QueryError::RateLimited { response, retry_after },
+            500..=599 => QueryError::ServerError { response },
+            _ => QueryError::Request { response },
+        }
+    }
+
+    /// error response returned by the server, if this error was caused by one
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            QueryError::NotFound { response }
+            | QueryError::Conflict { response }
+            | QueryError::Validation { response, .. }
+            | QueryError::RateLimited { response, .. }
+            | QueryError::ServerError { response }
+            | QueryError::Request { response } => Some(response),
+            _ => None,
+        }
+    }
+
+    /// check whether the failed request might succeed if it is sent again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            QueryError::RateLimited { .. } | QueryError::ServerError { .. } => true,
+            _ => self.is_transient(),
+        }
+    }
+
+    /// check whether the server rejected the request as invalid (HTTP 4xx)
+    pub fn is_client_error(&self) -> bool {
+        self.response()
+            .map(|response| (400..500).contains(&response.status))
+            .unwrap_or(false)
+    }
+
+    /// check whether this error was caused by a (potentially) transient networking issue
+    pub(crate) fn is_transient(&self) -> bool {
+        match self {
+            QueryError::Networking { error } => error.is_timeout() || error.is_connect(),
+            QueryError::Transport { transient, .. } => *transient,
+            _ => false,
+        }
+    }
+}
+
+/// unsuccessful HTTP response returned by the anitya server
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+    pub method: RequestMethod,
+    pub url: Url,
+    pub status: u16,
+    /// original response body
+    pub body: String,
+    /// error message extracted from the response body, if any
+    pub message: Option<String>,
+}
+
+impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = self.message.as_deref().unwrap_or(&self.body);
+        write!(
+            f,
+            "HTTP {} for {:?} {} / {}",
+            self.status, self.method, self.url, message
+        )
+    }
+}
+
+/// extract the error message and field errors from a JSON error response body
+///
+/// anitya reports errors as `{"error": "..."}`, or as `{"message": "..."}`, or with error messages
+/// for individual fields as `{"message": {"field": "..."}}` or `{"errors": {"field": ["..."]}}`.
+fn parse_error_body(body: &str) -> (Option<String>, BTreeMap<String, Vec<String>>) {
+    let mut message = None;
+    let mut errors = BTreeMap::new();
+
+    let object = match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(object)) => object,
+        _ => return (message, errors),
+    };
+
+    for key in ["error", "message", "errors"] {
+        match object.get(key) {
+            Some(Value::String(string)) => {
+                message.get_or_insert_with(|| string.clone());
+            },
+            Some(Value::Object(fields)) => {
+                for (field, value) in fields {
+                    errors.insert(field.clone(), messages(value));
+                }
+            },
+            Some(Value::Array(values)) => {
+                let joined: Vec<String> = values.iter().flat_map(messages).collect();
+                message.get_or_insert_with(|| joined.join("; "));
+            },
+            _ => {},
+        }
+    }
+
+    if message.is_none() && !errors.is_empty() {
+        let fields: Vec<&str> = errors.keys().map(String::as_str).collect();
+        message = Some(format!("Invalid fields: {}", fields.join(", ")));
+    }
+
+    (message, errors)
+}
+
+fn messages(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(string) => vec![string.clone()],
+        Value::Array(values) => values.iter().flat_map(messages).collect(),
+        other => vec![other.to_string()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_for(status: u16, body: &str) -> QueryError {
+        let url = Url::parse("https://release-monitoring.org/api/v2/projects/").unwrap();
+        let response = HttpResponse {
+            status,
+            headers: vec![(String::from("Retry-After"), String::from("30"))],
+            body: body.to_string(),
+        };
+        QueryError::from_response(RequestMethod::POST, url, response)
+    }
+
+    #[test]
+    fn conflict() {
+        let error = error_for(409, r#"{"error": "Unable to create project since it already exists."}"#);
+
+        assert!(matches!(error, QueryError::Conflict { .. }));
+        assert!(error.is_client_error());
+        assert!(!error.is_retryable());
+
+        let response = error.response().unwrap();
+        assert_eq!(response.method, RequestMethod::POST);
+        assert_eq!(
+            response.message.as_deref(),
+            Some("Unable to create project since it already exists.")
+        );
+    }
+
+    #[test]
+    fn not_found() {
+        let error = error_for(404, r#"{"error": "No such project", "output": "notok"}"#);
+
+        assert!(matches!(error, QueryError::NotFound { .. }));
+        assert_eq!(error.response().unwrap().message.as_deref(), Some("No such project"));
+    }
+
+    #[test]
+    fn validation() {
+        let error = error_for(
+            400,
+            r#"{"message": {"homepage": "Missing required parameter in the JSON body", "backend": ["Not a valid choice"]}}"#,
+        );
+
+        match error {
+            QueryError::Validation { ref errors, .. } => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors["backend"], vec![String::from("Not a valid choice")]);
+            },
+            _ => panic!("unexpected error: {:?}", error),
+        }
+
+        assert_eq!(
+            error.response().unwrap().message.as_deref(),
+            Some("Invalid fields: backend, homepage")
+        );
+    }
+
+    #[test]
+    fn rate_limited_and_server_error() {
+        let error = error_for(429, "Too Many Requests");
+        assert!(matches!(
+            error,
+            QueryError::RateLimited {
+                retry_after: Some(_),
+                ..
+            }
+        ));
+        assert!(error.is_retryable());
+        assert!(error.is_client_error());
+
+        let error = error_for(502, "<html>Bad Gateway</html>");
+        assert!(matches!(error, QueryError::ServerError { .. }));
+        assert!(error.is_retryable());
+        assert!(!error.is_client_error());
+        assert_eq!(error.response().unwrap().message, None);
+    }
 }
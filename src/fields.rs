@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+/// fields of a response that are not known to this crate
+pub type Extra = BTreeMap<String, serde_json::Value>;
+
+/// response model that keeps fields which are not known to this crate
+pub trait ExtraFields {
+    /// names of unknown fields, including those of nested models (for example, `items[3].foo`)
+    fn unknown_fields(&self) -> Vec<String>;
+}
+
+/// how unknown fields in responses are handled, set with
+/// [`ClientBuilder::with_strict_mode`](crate::ClientBuilder::with_strict_mode)
+///
+/// Unknown fields are always kept in the `extra` map of response models. Enabling strict mode can
+/// help with noticing changes of the anitya API early.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StrictMode {
+    /// silently accept unknown fields
+    #[default]
+    Ignore,
+    /// log a warning for every response that contains unknown fields
+    Warn,
+    /// return [`QueryError::UnknownFields`](crate::QueryError::UnknownFields) for responses that
+    /// contain unknown fields
+    Error,
+}
+
+/// utility function for collecting the unknown fields of a list of nested models
+pub(crate) fn nested<T: ExtraFields>(name: &str, items: &[T]) -> Vec<String> {
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(index, item)| {
+            item.unknown_fields()
+                .into_iter()
+                .map(move |field| format!("{}[{}].{}", name, index, field))
+        })
+        .collect()
+}
@@ -2,17 +2,59 @@
 
 #![warn(missing_debug_implementations)]
 
+pub mod cache;
+
+pub mod cassette;
+
+mod checkpoint;
+pub use checkpoint::{Checkpoint, PartialResults, Resumable};
+
 mod client;
 pub use client::{AnityaClient, ClientBuildError, ClientBuilder};
 
+mod consistency;
+pub use consistency::{ConsistencyMode, ConsistentResults, ItemKey};
+
+mod ensure;
+pub use ensure::{Difference, Ensured};
+
 mod errors;
-pub use errors::QueryError;
+pub use errors::{ErrorResponse, QueryError};
+
+mod fields;
+pub use fields::{Extra, ExtraFields, StrictMode};
+
+mod lookup;
+
+mod progress;
+pub use progress::{Progress, ProgressCallback};
+
+mod ratelimit;
+pub use ratelimit::RateLimit;
 
 mod request;
 pub use request::*;
 
+mod retry;
+pub use retry::RetryPolicy;
+
+mod timestamp;
+pub use timestamp::Timestamp;
+
+pub mod transport;
+
+mod types;
+pub use types::{Backend, Ecosystem, ProjectId, ProjectRef, VersionScheme};
+
+mod validation;
+pub use validation::Diagnostic;
+
 // HTTP API v2
 pub mod v2;
 
 // HTTP API v1
 pub mod v1;
+
+// synchronous client
+#[cfg(feature = "blocking")]
+pub mod blocking;
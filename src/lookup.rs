@@ -0,0 +1,75 @@
+use serde::de::DeserializeOwned;
+
+use crate::client::AnityaClient;
+use crate::errors::QueryError;
+use crate::request::SingleRequest;
+use crate::types::{Ecosystem, ProjectId, ProjectRef};
+use crate::v1::{PackageProjectQuery, ProjectIdQuery};
+use crate::v2::{Package, Project, ProjectQuery, VersionQuery, Versions};
+
+/// convenience methods for looking up single projects and resolving references
+impl AnityaClient {
+    /// look up a project by its ID
+    pub async fn get_project(&self, id: ProjectId) -> Result<Option<Project>, QueryError> {
+        let project = self.lookup(&ProjectIdQuery::new(id)).await?;
+        project.map(Project::try_from).transpose()
+    }
+
+    /// look up a project by its name within an ecosystem
+    pub async fn get_project_by_ecosystem(
+        &self,
+        ecosystem: &Ecosystem,
+        name: &str,
+    ) -> Result<Option<Project>, QueryError> {
+        let query = ProjectQuery::new()
+            .ecosystem(ecosystem.clone())
+            .name(name.to_string())
+            .items_per_page(1);
+
+        let page = self.page(&query, 1).await?;
+        Ok(page.items.into_iter().next())
+    }
+
+    /// look up the project that a distribution package is mapped to
+    pub async fn get_project_for_package(
+        &self,
+        distribution: &str,
+        package: &str,
+    ) -> Result<Option<Project>, QueryError> {
+        let query = PackageProjectQuery::new(distribution.to_string(), package.to_string());
+        let project = self.lookup(&query).await?;
+        project.map(Project::try_from).transpose()
+    }
+
+    /// resolve a reference to the full project
+    pub async fn resolve_project(&self, reference: &ProjectRef) -> Result<Option<Project>, QueryError> {
+        self.get_project_by_ecosystem(&reference.ecosystem, &reference.name)
+            .await
+    }
+
+    /// resolve a package to the full project that it is mapped to
+    pub async fn resolve_package(&self, package: &Package) -> Result<Option<Project>, QueryError> {
+        self.resolve_project(&package.project_ref()).await
+    }
+
+    /// resolve a package to the versions of the project that it is mapped to
+    pub async fn resolve_package_versions(&self, package: &Package) -> Result<Option<Versions>, QueryError> {
+        match self.resolve_package(package).await? {
+            Some(project) => self.lookup(&VersionQuery::new(project.id)).await,
+            None => Ok(None),
+        }
+    }
+
+    /// send a request for a single resource, and return `None` if it does not exist
+    async fn lookup<R, P, T>(&self, request: &R) -> Result<Option<T>, QueryError>
+    where
+        R: SingleRequest<P, T>,
+        T: DeserializeOwned,
+    {
+        match self.request(request).await {
+            Ok(result) => Ok(Some(result)),
+            Err(QueryError::NotFound { .. }) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+/// progress of a paginated request
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    /// number of pages that have been fetched so far
+    pub pages_done: u32,
+    /// total number of pages (which is only known once the first page has been fetched)
+    pub total_pages: Option<u32>,
+    /// number of items that have been fetched so far
+    pub items_fetched: usize,
+    /// time since the paginated request was started
+    pub elapsed: Duration,
+    /// estimated time until all remaining pages have been fetched
+    pub eta: Option<Duration>,
+}
+
+/// callback that is called with the current [`Progress`] of a paginated request
+pub type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// construct a [`ProgressCallback`] that publishes progress updates to a `watch` channel
+pub(crate) fn watch_callback() -> (ProgressCallback, watch::Receiver<Progress>) {
+    let (sender, receiver) = watch::channel(Progress::default());
+    let callback: ProgressCallback = Arc::new(move |progress: &Progress| {
+        // errors only indicate that there are no receivers left
+        let _ = sender.send(progress.clone());
+    });
+    (callback, receiver)
+}
+
+/// helper for keeping track of the progress of a paginated request
+#[derive(Debug)]
+pub(crate) struct ProgressTracker {
+    start: Instant,
+    delay: Option<Duration>,
+    skipped: u32,
+    progress: Progress,
+}
+
+impl ProgressTracker {
+    /// start tracking progress, with `skipped` pages that were already fetched previously
+    pub(crate) fn new(delay: Option<Duration>, skipped: u32) -> Self {
+        ProgressTracker {
+            start: Instant::now(),
+            delay,
+            skipped,
+            progress: Progress {
+                pages_done: skipped,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// current progress (including the "zero progress" state before the first page is fetched)
+    pub(crate) fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// record that another page with the given number of items has been fetched
+    pub(crate) fn page_done(&mut self, items: usize, total_pages: u32) -> &Progress {
+        let elapsed = self.start.elapsed();
+
+        let pages_done = self.progress.pages_done + 1;
+        let remaining = total_pages.saturating_sub(pages_done);
+
+        // requests are spaced out by at least the configured delay
+        let per_page = (elapsed / (pages_done - self.skipped)).max(self.delay.unwrap_or_default());
+
+        self.progress = Progress {
+            pages_done,
+            total_pages: Some(total_pages),
+            items_fetched: self.progress.items_fetched + items,
+            elapsed,
+            eta: Some(per_page * remaining),
+        };
+
+        &self.progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn eta() {
+        let mut tracker = ProgressTracker::new(Some(Duration::from_secs(2)), 0);
+        assert_eq!(tracker.progress().total_pages, None);
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let progress = tracker.page_done(25, 5).clone();
+        assert_eq!(progress.pages_done, 1);
+        assert_eq!(progress.items_fetched, 25);
+        assert_eq!(progress.elapsed, Duration::from_secs(1));
+        // fetching a page took less time than the configured delay
+        assert_eq!(progress.eta, Some(Duration::from_secs(8)));
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        let progress = tracker.page_done(25, 5).clone();
+        assert_eq!(progress.pages_done, 2);
+        assert_eq!(progress.items_fetched, 50);
+        assert_eq!(progress.eta, Some(Duration::from_secs(9)));
+    }
+}
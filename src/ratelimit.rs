@@ -0,0 +1,130 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// token bucket rate limit for HTTP requests
+///
+/// The bucket is refilled with `requests` tokens per `interval`, and can hold up to `burst`
+/// tokens (which defaults to `requests`). Every HTTP request made by the client (including
+/// retries) consumes one token, and waits for the bucket to be refilled if it is empty.
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+    requests: u32,
+    interval: Duration,
+    burst: u32,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, interval: Duration) -> Self {
+        let requests = requests.max(1);
+
+        RateLimit {
+            requests,
+            interval,
+            burst: requests,
+        }
+    }
+
+    /// maximum number of requests that can be made in a burst after a period of inactivity
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// number of tokens that are added to the bucket per second
+    fn rate(&self) -> f64 {
+        f64::from(self.requests) / self.interval.as_secs_f64()
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let bucket = Bucket {
+            tokens: f64::from(limit.burst),
+            updated: Instant::now(),
+        };
+
+        RateLimiter {
+            limit,
+            bucket: Mutex::new(bucket),
+        }
+    }
+
+    /// wait until a request is allowed to be sent
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().await;
+            let rate = self.limit.rate();
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+
+            bucket.tokens = (bucket.tokens + elapsed * rate).min(f64::from(self.limit.burst));
+            bucket.updated = now;
+
+            // the token is reserved immediately, so waiting requests are released in order
+            bucket.tokens -= 1.0;
+
+            if bucket.tokens < 0.0 {
+                Some(Duration::from_secs_f64(-bucket.tokens / rate))
+            } else {
+                None
+            }
+        };
+
+        if let Some(wait) = wait {
+            log::debug!("Rate limit reached, waiting for {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_then_throttle() {
+        let limiter = RateLimiter::new(RateLimit::new(2, Duration::from_secs(1)).burst(3));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(1000));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refill_after_idle() {
+        let limiter = RateLimiter::new(RateLimit::new(1, Duration::from_secs(1)));
+
+        limiter.acquire().await;
+        tokio::time::sleep(Duration::from_secs(10)).await;
+
+        // the bucket does not hold more than `burst` tokens
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+}
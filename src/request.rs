@@ -1,8 +1,11 @@
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::QueryError;
+use crate::progress::Progress;
+use crate::validation::Diagnostic;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum RequestMethod {
     GET,
     POST,
@@ -17,18 +20,49 @@ where
     fn body(&self) -> Result<Option<String>, QueryError>;
     fn parse(&self, string: &str) -> Result<P, QueryError>;
     fn extract(&self, page: P) -> T;
+
+    /// names of fields in the parsed response that are not known to this crate
+    fn unknown_fields(&self, _page: &P) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// check the request for problems before it is sent, and return all of them at once
+    fn validate(&self) -> Result<(), Vec<Diagnostic>> {
+        Ok(())
+    }
+
+    /// media type of the request body
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    /// whether the request needs to be authenticated with an API token
+    fn authenticated(&self) -> bool {
+        self.method() == RequestMethod::POST
+    }
 }
 
-pub trait PaginatedRequest<'a, P, T, S>
+pub trait PaginatedRequest<P, T, S>
 where
     P: Pagination,
     T: DeserializeOwned,
-    S: SingleRequest<P, T> + 'a,
+    S: SingleRequest<P, T>,
 {
-    fn page_request(&'a self, page: u32) -> S;
-    fn callback(&'a self, page: u32, pages: u32);
+    fn page_request(&self, page: u32) -> S;
+    fn report_progress(&self, progress: &Progress);
+
+    /// first page that is requested (this is only different from 1 for resumed requests)
+    fn first_page(&self) -> u32 {
+        1
+    }
+
+    /// request that is used for only determining the total number of items
+    fn count_request(&self) -> S {
+        self.page_request(1)
+    }
 }
 
 pub trait Pagination {
     fn pages(&self) -> u32;
+    fn total_items(&self) -> u32;
 }
@@ -0,0 +1,219 @@
+use std::time::{Duration, SystemTime};
+
+use crate::request::RequestMethod;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BACKOFF_BASE: Duration = Duration::from_millis(500);
+const DEFAULT_BACKOFF_CAP: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(300);
+const DEFAULT_RETRYABLE_STATUS: [u16; 5] = [429, 500, 502, 503, 504];
+
+/// policy for automatically retrying failed requests
+///
+/// Requests that fail with one of the configured HTTP status codes (or due to a timeout or
+/// connection error) are retried up to `max_attempts` times in total, with an exponentially
+/// increasing delay between attempts. If the server sends a `Retry-After` header, it takes
+/// precedence over the computed backoff delay, unless it asks for a longer delay than
+/// [`RetryPolicy::max_retry_after`], in which case the request fails immediately instead.
+///
+/// `POST` requests are not retried unless explicitly enabled with [`RetryPolicy::retry_posts`],
+/// since they are not guaranteed to be idempotent.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff_base: Duration,
+    backoff_cap: Duration,
+    max_retry_after: Duration,
+    jitter: bool,
+    retryable_status: Vec<u16>,
+    retry_posts: bool,
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff_base: DEFAULT_BACKOFF_BASE,
+            backoff_cap: DEFAULT_BACKOFF_CAP,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER,
+            jitter: true,
+            retryable_status: DEFAULT_RETRYABLE_STATUS.to_vec(),
+            retry_posts: false,
+        }
+    }
+
+    /// maximum number of attempts per request, including the first one
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// delay before the first retry, which is doubled for every subsequent attempt
+    pub fn backoff_base(mut self, backoff_base: Duration) -> Self {
+        self.backoff_base = backoff_base;
+        self
+    }
+
+    /// upper limit for the computed backoff delay
+    pub fn backoff_cap(mut self, backoff_cap: Duration) -> Self {
+        self.backoff_cap = backoff_cap;
+        self
+    }
+
+    /// longest delay requested with a `Retry-After` header that is still waited for
+    pub fn max_retry_after(mut self, max_retry_after: Duration) -> Self {
+        self.max_retry_after = max_retry_after;
+        self
+    }
+
+    /// randomize backoff delays to avoid synchronized retries from multiple clients
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// HTTP status codes that are considered to indicate a transient failure
+    pub fn retryable_status(mut self, retryable_status: Vec<u16>) -> Self {
+        self.retryable_status = retryable_status;
+        self
+    }
+
+    /// allow retrying `POST` requests
+    pub fn retry_posts(mut self, retry_posts: bool) -> Self {
+        self.retry_posts = retry_posts;
+        self
+    }
+
+    /// check whether a request with the given method can be retried at all
+    pub(crate) fn allows(&self, method: &RequestMethod, attempt: u32) -> bool {
+        if attempt >= self.max_attempts {
+            return false;
+        }
+
+        match method {
+            RequestMethod::GET => true,
+            RequestMethod::POST => self.retry_posts,
+        }
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_status.contains(&status)
+    }
+
+    /// compute the delay before the next attempt after a retryable error response, taking the
+    /// `Retry-After` delay requested by the server into account
+    ///
+    /// Returns `None` if the requested delay is too long to wait for.
+    pub(crate) fn delay(&self, retry_after: Option<Duration>, attempt: u32) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if delay > self.max_retry_after => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// compute the delay before the next attempt, after `attempt` attempts have failed
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .backoff_base
+            .checked_mul(factor)
+            .unwrap_or(self.backoff_cap)
+            .min(self.backoff_cap);
+
+        if self.jitter {
+            // "equal jitter": wait at least half of the computed delay
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// parse the value of a `Retry-After` header into a delay
+///
+/// The header can either contain a number of seconds, or an HTTP date.
+pub(crate) fn retry_after(value: Option<&str>) -> Option<Duration> {
+    let value = value?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_without_jitter() {
+        let policy = RetryPolicy::new()
+            .backoff_base(Duration::from_secs(1))
+            .backoff_cap(Duration::from_secs(5))
+            .jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+        assert_eq!(policy.backoff(100), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn backoff_with_jitter() {
+        let policy = RetryPolicy::new()
+            .backoff_base(Duration::from_secs(2))
+            .backoff_cap(Duration::from_secs(60));
+
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_secs(2));
+            assert!(delay <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn attempts_and_methods() {
+        let policy = RetryPolicy::new().max_attempts(2);
+
+        assert!(policy.allows(&RequestMethod::GET, 1));
+        assert!(!policy.allows(&RequestMethod::GET, 2));
+        assert!(!policy.allows(&RequestMethod::POST, 1));
+
+        let policy = policy.retry_posts(true);
+        assert!(policy.allows(&RequestMethod::POST, 1));
+    }
+
+    #[test]
+    fn retry_after_limit() {
+        let policy = RetryPolicy::new()
+            .backoff_base(Duration::from_secs(1))
+            .max_retry_after(Duration::from_secs(60))
+            .jitter(false);
+
+        assert_eq!(policy.delay(None, 1), Some(Duration::from_secs(1)));
+        assert_eq!(
+            policy.delay(Some(Duration::from_secs(60)), 1),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(policy.delay(Some(Duration::from_secs(86400)), 1), None);
+    }
+
+    #[test]
+    fn retry_after_header() {
+        assert_eq!(retry_after(None), None);
+        assert_eq!(retry_after(Some("120")), Some(Duration::from_secs(120)));
+        assert_eq!(retry_after(Some("Wed, 21 Oct 2015 07:28:00 GMT")), Some(Duration::ZERO));
+        assert_eq!(retry_after(Some("soon")), None);
+    }
+}
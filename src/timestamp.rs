@@ -0,0 +1,155 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// earliest supported timestamp (0001-01-01T00:00:00Z)
+const MIN_SECS: f64 = -62_135_596_800.0;
+/// latest supported timestamp (9999-12-31T23:59:59Z)
+const MAX_SECS: f64 = 253_402_300_799.0;
+
+/// point in time, as returned by anitya (seconds since the UNIX epoch, with fractional part)
+///
+/// The raw value is available with [`Timestamp::as_f64`]. Conversions into other types are
+/// rounded to whole microseconds, which is the precision of timestamps that are stored by anitya.
+/// With the `time` or `chrono` features, timestamps can also be converted into the datetime types
+/// of those crates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Timestamp(f64);
+
+impl Timestamp {
+    /// construct a timestamp from seconds since the UNIX epoch
+    ///
+    /// Returns `None` for values that are not finite or that are outside the years 1 to 9999.
+    pub fn new(secs: f64) -> Option<Self> {
+        if secs.is_finite() && (MIN_SECS..=MAX_SECS).contains(&secs) {
+            Some(Timestamp(secs))
+        } else {
+            None
+        }
+    }
+
+    /// seconds since the UNIX epoch
+    pub fn as_f64(self) -> f64 {
+        self.0
+    }
+
+    /// whole seconds since the UNIX epoch and nanoseconds, rounded to whole microseconds
+    fn parts(self) -> (i64, u32) {
+        let micros = (self.0 * 1_000_000.0).round() as i64;
+        let secs = micros.div_euclid(1_000_000);
+        let nanos = micros.rem_euclid(1_000_000) as u32 * 1_000;
+        (secs, nanos)
+    }
+
+    pub fn to_system_time(self) -> SystemTime {
+        let (secs, nanos) = self.parts();
+
+        if secs >= 0 {
+            UNIX_EPOCH + Duration::new(secs as u64, nanos)
+        } else {
+            UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs()) + Duration::from_nanos(u64::from(nanos))
+        }
+    }
+
+    #[cfg(feature = "time")]
+    pub fn to_offset_date_time(self) -> time::OffsetDateTime {
+        let (secs, nanos) = self.parts();
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(secs) * 1_000_000_000 + i128::from(nanos))
+            .expect("Timestamp out of range.")
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_date_time(self) -> chrono::DateTime<chrono::Utc> {
+        use chrono::TimeZone;
+
+        let (secs, nanos) = self.parts();
+        chrono::Utc
+            .timestamp_opt(secs, nanos)
+            .single()
+            .expect("Timestamp out of range.")
+    }
+
+    /// time that has passed since this timestamp (zero for timestamps in the future)
+    pub fn elapsed(self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.to_system_time())
+            .unwrap_or_default()
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        Timestamp::new(secs).ok_or_else(|| serde::de::Error::custom(format!("Timestamp out of range: {}", secs)))
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_system_time()
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_offset_date_time()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_date_time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounding() {
+        // 1412174944.123457 can not be represented exactly as f64
+        let timestamp = Timestamp::new(1412174944.123457).unwrap();
+        assert_eq!(timestamp.parts(), (1412174944, 123_457_000));
+
+        let timestamp = Timestamp::new(-1.25).unwrap();
+        assert_eq!(timestamp.parts(), (-2, 750_000_000));
+        assert_eq!(timestamp.to_system_time(), UNIX_EPOCH - Duration::from_millis(1250));
+    }
+
+    #[test]
+    fn range() {
+        assert!(Timestamp::new(f64::NAN).is_none());
+        assert!(Timestamp::new(f64::INFINITY).is_none());
+        assert!(Timestamp::new(1e12).is_none());
+        assert!(serde_json::from_str::<Timestamp>("1e12").is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time() {
+        let timestamp = Timestamp::new(1412174944.5).unwrap();
+        let datetime = timestamp.to_offset_date_time();
+        assert_eq!(datetime.unix_timestamp(), 1412174944);
+        assert_eq!(datetime.millisecond(), 500);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        let timestamp = Timestamp::new(1412174944.5).unwrap();
+        let datetime = timestamp.to_date_time();
+        assert_eq!(datetime.timestamp(), 1412174944);
+        assert_eq!(datetime.timestamp_subsec_millis(), 500);
+    }
+}
@@ -0,0 +1,158 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::errors::QueryError;
+use crate::request::RequestMethod;
+
+/// HTTP request as it is handed to a [`Transport`]
+///
+/// The value of the `Authorization` header is redacted from the [`Debug`] output.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HttpRequest {
+    pub method: RequestMethod,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    /// look up the value of a header (header names are matched case-insensitively)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+impl Debug for HttpRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(key, value)| {
+                if key.eq_ignore_ascii_case(AUTHORIZATION.as_str()) {
+                    (key.as_str(), "(Authorization header)")
+                } else {
+                    (key.as_str(), value.as_str())
+                }
+            })
+            .collect();
+
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("body", &self.body)
+            .finish()
+    }
+}
+
+/// HTTP response as it is returned from a [`Transport`]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// look up the value of a header (header names are matched case-insensitively)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// HTTP stack that is used by [`AnityaClient`](crate::AnityaClient) to send requests
+///
+/// The default implementation is [`ReqwestTransport`]. Custom implementations can be plugged
+/// into the client with [`ClientBuilder::with_transport`](crate::ClientBuilder::with_transport).
+/// Errors that are specific to a custom implementation should be reported as
+/// [`QueryError::Transport`], with `transient` set for failures (like timeouts) that might not
+/// occur again, so that the request is retried according to the client's
+/// [`RetryPolicy`](crate::RetryPolicy).
+///
+/// Note that HTTP error responses are not errors at this level, they are handled by the client.
+pub trait Transport: Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>>;
+}
+
+impl<T> Transport for Arc<T>
+where
+    T: Transport + ?Sized,
+{
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        (**self).send(request)
+    }
+}
+
+/// default [`Transport`] implementation based on [`reqwest`]
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    session: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Result<Self, reqwest::Error> {
+        let session = reqwest::ClientBuilder::new()
+            //.timeout()
+            .build()?;
+
+        Ok(ReqwestTransport { session })
+    }
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(session: reqwest::Client) -> Self {
+        ReqwestTransport { session }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        Box::pin(async move {
+            let mut builder = match request.method {
+                RequestMethod::GET => self.session.get(request.url),
+                RequestMethod::POST => self.session.post(request.url),
+            };
+
+            for (key, value) in request.headers {
+                if key.eq_ignore_ascii_case(AUTHORIZATION.as_str()) {
+                    // mark the API token as sensitive; invalid values are reported by reqwest
+                    if let Ok(mut value) = HeaderValue::from_str(&value) {
+                        value.set_sensitive(true);
+                        builder = builder.header(AUTHORIZATION, value);
+                        continue;
+                    }
+                }
+
+                builder = builder.header(key, value);
+            }
+
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder.send().await?;
+
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .filter_map(|(key, value)| Some((key.to_string(), value.to_str().ok()?.to_string())))
+                .collect();
+            let body = response.text().await?;
+
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
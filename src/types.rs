@@ -0,0 +1,196 @@
+//! typed values for identifiers and for fields that only accept a fixed set of strings
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// helper for defining enums that are (de)serialized from / to strings
+///
+/// Strings that do not correspond to any of the known variants are mapped to the `Other`
+/// variant, so values that were added in newer versions of anitya can still be handled.
+macro_rules! string_enum {
+    ($(#[$meta:meta])* $name:ident { $($(#[$variant_meta:meta])* $variant:ident => $string:literal,)* }) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// value that is not known to this version of the crate
+            Other(String),
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $string,)*
+                    $name::Other(value) => value,
+                }
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($string => $name::$variant,)*
+                    other => $name::Other(other.to_string()),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match $name::from(value.as_str()) {
+                    $name::Other(_) => $name::Other(value),
+                    known => known,
+                }
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok($name::from(String::deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+string_enum! {
+    /// backend that is used for checking a project for new versions
+    Backend {
+        BitBucket => "BitBucket",
+        Cpan => "CPAN (perl)",
+        Cran => "CRAN (R)",
+        CratesIo => "crates.io",
+        Custom => "custom",
+        Debian => "Debian project",
+        Drupal6 => "Drupal6",
+        Drupal7 => "Drupal7",
+        Freshmeat => "Freshmeat",
+        GitHub => "GitHub",
+        GitLab => "GitLab",
+        Gitea => "Gitea",
+        Gnome => "GNOME",
+        Gnu => "GNU project",
+        GoogleCode => "Google code",
+        Hackage => "Hackage",
+        Launchpad => "Launchpad",
+        MavenCentral => "Maven Central",
+        Npmjs => "npmjs",
+        Packagist => "Packagist",
+        Pagure => "pagure",
+        Pear => "PEAR",
+        Pecl => "PECL",
+        PyPI => "PyPI",
+        Rubygems => "Rubygems",
+        SourceForge => "Sourceforge",
+        SourceForgeGit => "SourceForge (git)",
+        SourceHut => "SourceHut",
+        Stackage => "Stackage",
+    }
+}
+
+string_enum! {
+    /// ecosystem of a project
+    ///
+    /// Projects that do not belong to any ecosystem use their homepage as ecosystem instead,
+    /// which is represented by the `Other` variant.
+    Ecosystem {
+        CratesIo => "crates.io",
+        Maven => "maven",
+        Npm => "npm",
+        PyPI => "pypi",
+        Rubygems => "rubygems",
+    }
+}
+
+string_enum! {
+    /// scheme that is used for sorting the versions of a project
+    VersionScheme {
+        Rpm => "RPM",
+        Semantic => "Semantic",
+        Calendar => "Calendar",
+        Pep440 => "PEP 440",
+    }
+}
+
+/// ID of a project
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct ProjectId(pub u32);
+
+impl Display for ProjectId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u32> for ProjectId {
+    fn from(id: u32) -> Self {
+        ProjectId(id)
+    }
+}
+
+/// reference to a project by its ecosystem and name, which uniquely identify it
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ProjectRef {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+}
+
+impl ProjectRef {
+    pub fn new(ecosystem: Ecosystem, name: String) -> Self {
+        ProjectRef { ecosystem, name }
+    }
+}
+
+impl Display for ProjectRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.ecosystem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(Backend::from("GitHub"), Backend::GitHub);
+        assert_eq!(Backend::MavenCentral.as_str(), "Maven Central");
+        assert_eq!(Ecosystem::from("crates.io"), Ecosystem::CratesIo);
+        assert_eq!(VersionScheme::Pep440.to_string(), "PEP 440");
+    }
+
+    #[test]
+    fn other_values() {
+        // values are matched exactly
+        assert_eq!(Backend::from("Github"), Backend::Other(String::from("Github")));
+        assert_eq!(
+            Ecosystem::from("https://example.org"),
+            Ecosystem::Other(String::from("https://example.org"))
+        );
+        assert_eq!(VersionScheme::Other(String::from("semver")).as_str(), "semver");
+    }
+
+    #[test]
+    fn serde() {
+        let backend: Backend = serde_json::from_str(r#""crates.io""#).unwrap();
+        assert_eq!(backend, Backend::CratesIo);
+        assert_eq!(serde_json::to_string(&backend).unwrap(), r#""crates.io""#);
+
+        let scheme: VersionScheme = serde_json::from_str(r#""Year-Month""#).unwrap();
+        assert_eq!(scheme, VersionScheme::Other(String::from("Year-Month")));
+        assert_eq!(serde_json::to_string(&scheme).unwrap(), r#""Year-Month""#);
+    }
+}
@@ -1 +1,51 @@
+//! requests for endpoints of the legacy v1 API
+//!
+//! Some functionality of anitya is only available with the v1 API (for example, searching
+//! projects by name pattern or by homepage, or looking up projects by their ID or by their
+//! mapping to a distribution package).
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Serialize;
+
+use crate::errors::QueryError;
+
+mod distros;
+pub use distros::*;
+
+mod projects;
+pub use projects::*;
+
+mod versions;
+pub use versions::*;
+
+/// characters that are not percent-encoded in path segments
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// utility function for percent-encoding a single path segment
+fn encode_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, SEGMENT).to_string()
+}
+
+/// utility function for appending optional query parameters to a request path
+fn with_params<Q: Serialize>(path: &str, params: &Q) -> Result<String, QueryError> {
+    let params = serde_url_params::to_string(params)?;
+
+    if params.is_empty() {
+        Ok(path.to_string())
+    } else {
+        Ok(format!("{}?{}", path, params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_segment;
+
+    #[test]
+    fn segments() {
+        assert_eq!(encode_segment("python-requests"), "python-requests");
+        assert_eq!(encode_segment("rust-gtk4_0.8"), "rust-gtk4_0.8");
+        assert_eq!(encode_segment("golang-github-foo/bar"), "golang-github-foo%2Fbar");
+        assert_eq!(encode_segment("Fedora EPEL"), "Fedora%20EPEL");
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::QueryError;
+use crate::fields::{Extra, ExtraFields};
+use crate::request::{RequestMethod, SingleRequest};
+
+use super::with_params;
+
+#[derive(Debug, Default, Serialize)]
+pub struct DistroNamesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+}
+
+impl DistroNamesQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// only return names of distributions that match this pattern (with `*` as wildcard)
+    pub fn pattern(mut self, pattern: String) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DistroNames {
+    pub distro: Vec<String>,
+    pub total: u32,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for DistroNames {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
+}
+
+impl SingleRequest<DistroNames, Vec<String>> for DistroNamesQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        with_params("/api/distro/names/", self)
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<DistroNames, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: DistroNames) -> Vec<String> {
+        page.distro
+    }
+
+    fn unknown_fields(&self, page: &DistroNames) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
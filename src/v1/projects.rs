@@ -0,0 +1,336 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::QueryError;
+use crate::fields::{nested, Extra, ExtraFields};
+use crate::request::{RequestMethod, SingleRequest};
+use crate::timestamp::Timestamp;
+use crate::types::{Backend, Ecosystem, ProjectId};
+
+use super::{encode_segment, with_params};
+
+/// project, as it is returned by endpoints of the v1 API
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Project {
+    pub id: ProjectId,
+    pub name: String,
+    pub homepage: String,
+    pub regex: Option<String>,
+    pub backend: Backend,
+    pub version_url: Option<String>,
+    pub version: Option<String>,
+    pub versions: Vec<String>,
+    pub stable_versions: Vec<String>,
+    pub created_on: Option<Timestamp>,
+    pub updated_on: Option<Timestamp>,
+    pub ecosystem_name: Ecosystem,
+    /// mappings of this project to distribution packages (only included for single projects)
+    #[serde(default)]
+    pub packages: Vec<PackageMapping>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for Project {
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self.extra.keys().cloned().collect();
+        fields.extend(nested("packages", &self.packages));
+        fields
+    }
+}
+
+/// conversion into the API v2 model
+///
+/// This fails with [`QueryError::MissingField`] if the API v1 response did not include the
+/// timestamps, which are always present in the API v2 model.
+impl TryFrom<Project> for crate::v2::Project {
+    type Error = QueryError;
+
+    fn try_from(project: Project) -> Result<Self, Self::Error> {
+        let missing = |field| QueryError::MissingField { field };
+
+        Ok(crate::v2::Project {
+            backend: project.backend,
+            created_on: project.created_on.ok_or_else(|| missing("created_on"))?,
+            ecosystem: project.ecosystem_name,
+            homepage: project.homepage,
+            id: project.id,
+            name: project.name,
+            regex: project.regex,
+            updated_on: project.updated_on.ok_or_else(|| missing("updated_on"))?,
+            version: project.version,
+            version_url: project.version_url,
+            versions: project.versions,
+            stable_versions: project.stable_versions,
+            extra: project.extra,
+        })
+    }
+}
+
+/// mapping of a project to a package in a distribution
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PackageMapping {
+    pub distro: String,
+    pub package_name: String,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for PackageMapping {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProjectsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<String>,
+}
+
+impl ProjectsQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// only return projects with names that match this pattern (with `*` as wildcard)
+    pub fn pattern(mut self, pattern: String) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// only return projects with this homepage
+    pub fn homepage(mut self, homepage: String) -> Self {
+        self.homepage = Some(homepage);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Projects {
+    pub projects: Vec<Project>,
+    pub total: u32,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for Projects {
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self.extra.keys().cloned().collect();
+        fields.extend(nested("projects", &self.projects));
+        fields
+    }
+}
+
+impl SingleRequest<Projects, Vec<Project>> for ProjectsQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        with_params("/api/projects/", self)
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<Projects, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: Projects) -> Vec<Project> {
+        page.projects
+    }
+
+    fn unknown_fields(&self, page: &Projects) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ProjectNamesQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+}
+
+impl ProjectNamesQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// only return project names that match this pattern (with `*` as wildcard)
+    pub fn pattern(mut self, pattern: String) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ProjectNames {
+    pub projects: Vec<String>,
+    pub total: u32,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for ProjectNames {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
+}
+
+impl SingleRequest<ProjectNames, Vec<String>> for ProjectNamesQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        with_params("/api/projects/names/", self)
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<ProjectNames, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: ProjectNames) -> Vec<String> {
+        page.projects
+    }
+
+    fn unknown_fields(&self, page: &ProjectNames) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
+
+/// look up a project by its ID
+#[derive(Debug)]
+pub struct ProjectIdQuery {
+    id: ProjectId,
+}
+
+impl ProjectIdQuery {
+    pub fn new(id: ProjectId) -> Self {
+        ProjectIdQuery { id }
+    }
+}
+
+impl SingleRequest<Project, Project> for ProjectIdQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        Ok(format!("/api/project/{}/", self.id))
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<Project, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: Project) -> Project {
+        page
+    }
+
+    fn unknown_fields(&self, page: &Project) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
+
+/// look up the project that a distribution package is mapped to
+#[derive(Debug)]
+pub struct PackageProjectQuery {
+    distribution: String,
+    package: String,
+}
+
+impl PackageProjectQuery {
+    pub fn new(distribution: String, package: String) -> Self {
+        PackageProjectQuery { distribution, package }
+    }
+}
+
+impl SingleRequest<Project, Project> for PackageProjectQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        Ok(format!(
+            "/api/project/{}/{}/",
+            encode_segment(&self.distribution),
+            encode_segment(&self.package)
+        ))
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<Project, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: Project) -> Project {
+        page
+    }
+
+    fn unknown_fields(&self, page: &Project) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
+
+/// look up a project by its name within an ecosystem
+#[derive(Debug)]
+pub struct EcosystemProjectQuery {
+    ecosystem: Ecosystem,
+    name: String,
+}
+
+impl EcosystemProjectQuery {
+    pub fn new(ecosystem: Ecosystem, name: String) -> Self {
+        EcosystemProjectQuery { ecosystem, name }
+    }
+}
+
+impl SingleRequest<Project, Project> for EcosystemProjectQuery {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::GET
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        Ok(format!(
+            "/api/by_ecosystem/{}/{}/",
+            encode_segment(self.ecosystem.as_str()),
+            encode_segment(&self.name)
+        ))
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(None)
+    }
+
+    fn parse(&self, string: &str) -> Result<Project, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: Project) -> Project {
+        page
+    }
+
+    fn unknown_fields(&self, page: &Project) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+use crate::errors::QueryError;
+use crate::fields::ExtraFields;
+use crate::request::{RequestMethod, SingleRequest};
+use crate::types::ProjectId;
+
+use super::Project;
+
+/// check for new versions of a project, and return the updated project
+///
+/// This request is sent as a form instead of as JSON, and it does not require authentication.
+#[derive(Debug, Serialize)]
+pub struct VersionCheckRequest {
+    id: ProjectId,
+}
+
+impl VersionCheckRequest {
+    pub fn new(id: ProjectId) -> Self {
+        VersionCheckRequest { id }
+    }
+}
+
+impl SingleRequest<Project, Project> for VersionCheckRequest {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::POST
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        Ok(String::from("/api/version/get"))
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(Some(serde_url_params::to_string(self)?))
+    }
+
+    fn parse(&self, string: &str) -> Result<Project, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: Project) -> Project {
+        page
+    }
+
+    fn unknown_fields(&self, page: &Project) -> Vec<String> {
+        page.unknown_fields()
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/x-www-form-urlencoded"
+    }
+
+    fn authenticated(&self) -> bool {
+        false
+    }
+}
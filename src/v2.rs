@@ -1,3 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+use crate::fields::{nested, Extra, ExtraFields};
+use crate::request::Pagination;
+
 mod packages;
 pub use packages::*;
 
@@ -7,6 +12,40 @@ pub use projects::*;
 mod versions;
 pub use versions::*;
 
+/// single page of results of a paginated request
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub items_per_page: u32,
+    /// total number of items across all pages
+    pub total_items: u32,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl<T> ExtraFields for Page<T>
+where
+    T: ExtraFields,
+{
+    fn unknown_fields(&self) -> Vec<String> {
+        let mut fields: Vec<String> = self.extra.keys().cloned().collect();
+        fields.extend(nested("items", &self.items));
+        fields
+    }
+}
+
+impl<T> Pagination for Page<T> {
+    fn pages(&self) -> u32 {
+        num_pages(self.total_items, self.items_per_page)
+    }
+
+    fn total_items(&self) -> u32 {
+        self.total_items
+    }
+}
+
 /// utility function for calculating the number of result pages
 ///
 /// c.f. https://doc.rust-lang.org/std/primitive.u32.html#method.div_ceil
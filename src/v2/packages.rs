@@ -1,35 +1,53 @@
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
 
+use crate::checkpoint::Resumable;
+use crate::consistency::ItemKey;
 use crate::errors::QueryError;
-use crate::request::{PaginatedRequest, Pagination, RequestMethod, SingleRequest};
+use crate::fields::{Extra, ExtraFields};
+use crate::progress::{watch_callback, Progress, ProgressCallback};
+use crate::request::{PaginatedRequest, RequestMethod, SingleRequest};
+use crate::types::{Ecosystem, ProjectRef};
+
+use super::Page;
 
 const DEFAULT_PER_PAGE: u32 = 25;
 
 #[derive(Debug, Serialize)]
 pub struct NewPackageRequest {
-    distribution: String,
-    package_name: String,
-    project_name: String,
-    project_ecosystem: String,
+    pub(crate) distribution: String,
+    pub(crate) package_name: String,
+    pub(crate) project_name: String,
+    pub(crate) project_ecosystem: Ecosystem,
 }
 
 impl NewPackageRequest {
-    pub fn new(distribution: String, package_name: String, project_ecosystem: String, project_name: String) -> Self {
+    pub fn new(distribution: String, package_name: String, project: ProjectRef) -> Self {
         NewPackageRequest {
             distribution,
             package_name,
-            project_ecosystem,
-            project_name,
+            project_name: project.name,
+            project_ecosystem: project.ecosystem,
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NewPackage {
     pub distribution: String,
     pub name: String,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for NewPackage {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
 }
 
 impl SingleRequest<NewPackage, NewPackage> for NewPackageRequest {
@@ -52,13 +70,22 @@ impl SingleRequest<NewPackage, NewPackage> for NewPackageRequest {
     fn extract(&self, page: NewPackage) -> NewPackage {
         page
     }
+
+    fn unknown_fields(&self, page: &NewPackage) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct PackageQuery {
     distribution: Option<String>,
     name: Option<String>,
     items_per_page: u32,
-    callback: Option<Box<dyn Fn(u32, u32)>>,
+
+    #[serde(skip)]
+    start_page: Option<u32>,
+    #[serde(skip)]
+    progress: Option<ProgressCallback>,
 }
 
 impl PackageQuery {
@@ -67,7 +94,8 @@ impl PackageQuery {
             distribution: None,
             name: None,
             items_per_page: DEFAULT_PER_PAGE,
-            callback: None,
+            start_page: None,
+            progress: None,
         }
     }
 }
@@ -78,6 +106,7 @@ impl Debug for PackageQuery {
             .field("distribution", &self.distribution)
             .field("name", &self.name)
             .field("items_per_page", &self.items_per_page)
+            .field("start_page", &self.start_page)
             .finish()
     }
 }
@@ -103,39 +132,63 @@ impl PackageQuery {
         self.items_per_page = items_per_page.clamp(1, 250);
         self
     }
+
+    /// call the given function whenever the progress of this query changes
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// publish progress updates for this query to a [`watch`] channel
+    pub fn progress_watch(mut self) -> (Self, watch::Receiver<Progress>) {
+        let (callback, receiver) = watch_callback();
+        self.progress = Some(callback);
+        (self, receiver)
+    }
 }
 
 #[derive(Debug, Serialize)]
-pub struct PackagePageQuery<'a> {
+pub struct PackagePageQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
-    distribution: Option<&'a String>,
+    distribution: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<&'a String>,
+    name: Option<String>,
 
     page: u32,
     items_per_page: u32,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct PackagePage {
-    items: Vec<Package>,
-
-    #[allow(unused)]
-    page: u32,
-    items_per_page: u32,
-    total_items: u32,
-}
+pub type PackagePage = Page<Package>;
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Package {
     pub distribution: String,
     pub name: String,
     pub project: String,
-    pub ecosystem: String,
+    pub ecosystem: Ecosystem,
     pub version: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-impl<'a> SingleRequest<PackagePage, Vec<Package>> for PackagePageQuery<'a> {
+impl Package {
+    /// reference to the project that this package is mapped to
+    pub fn project_ref(&self) -> ProjectRef {
+        ProjectRef::new(self.ecosystem.clone(), self.project.clone())
+    }
+}
+
+impl ExtraFields for Package {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
+}
+
+impl SingleRequest<PackagePage, Vec<Package>> for PackagePageQuery {
     fn method(&self) -> RequestMethod {
         RequestMethod::GET
     }
@@ -155,27 +208,56 @@ impl<'a> SingleRequest<PackagePage, Vec<Package>> for PackagePageQuery<'a> {
     fn extract(&self, page: PackagePage) -> Vec<Package> {
         page.items
     }
+
+    fn unknown_fields(&self, page: &PackagePage) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
 
-impl Pagination for PackagePage {
-    fn pages(&self) -> u32 {
-        super::num_pages(self.total_items, self.items_per_page)
+impl ItemKey for Package {
+    type Key = (String, String);
+
+    fn key(&self) -> Self::Key {
+        (self.distribution.clone(), self.name.clone())
     }
 }
 
-impl<'a> PaginatedRequest<'a, PackagePage, Vec<Package>, PackagePageQuery<'a>> for PackageQuery {
-    fn page_request(&'a self, page: u32) -> PackagePageQuery<'a> {
+impl PaginatedRequest<PackagePage, Vec<Package>, PackagePageQuery> for PackageQuery {
+    fn page_request(&self, page: u32) -> PackagePageQuery {
         PackagePageQuery {
-            distribution: self.distribution.as_ref(),
-            name: self.name.as_ref(),
+            distribution: self.distribution.clone(),
+            name: self.name.clone(),
             page,
             items_per_page: self.items_per_page,
         }
     }
 
-    fn callback(&'a self, page: u32, pages: u32) {
-        if let Some(ref callback) = &self.callback {
-            callback(page, pages)
+    fn report_progress(&self, progress: &Progress) {
+        if let Some(ref callback) = &self.progress {
+            callback(progress)
+        }
+    }
+
+    fn first_page(&self) -> u32 {
+        self.start_page.unwrap_or(1)
+    }
+
+    fn count_request(&self) -> PackagePageQuery {
+        PackagePageQuery {
+            items_per_page: 1,
+            ..self.page_request(1)
         }
     }
 }
+
+impl Resumable for PackageQuery {
+    fn page_size(&self) -> u32 {
+        self.items_per_page
+    }
+
+    fn resume(mut self, last_page: u32, items_per_page: u32) -> Self {
+        self.items_per_page = items_per_page;
+        self.start_page = Some(last_page + 1);
+        self
+    }
+}
@@ -1,24 +1,36 @@
 use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
 
+use crate::checkpoint::Resumable;
+use crate::consistency::ItemKey;
 use crate::errors::QueryError;
-use crate::request::{PaginatedRequest, Pagination, RequestMethod, SingleRequest};
+use crate::fields::{Extra, ExtraFields};
+use crate::progress::{watch_callback, Progress, ProgressCallback};
+use crate::request::{PaginatedRequest, RequestMethod, SingleRequest};
+use crate::timestamp::Timestamp;
+use crate::types::{Backend, Ecosystem, ProjectId, ProjectRef};
+use crate::validation::{Diagnostic, Diagnostics};
+
+use super::Page;
 
 const DEFAULT_PER_PAGE: u32 = 25;
 
 #[derive(Debug, Serialize)]
 pub struct NewProjectRequest {
-    name: String,
-    homepage: String,
-    backend: String,
+    pub(crate) name: String,
+    pub(crate) homepage: String,
+    pub(crate) backend: Backend,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    version_url: Option<String>,
+    pub(crate) version_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     version_prefix: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    regex: Option<String>,
+    pub(crate) regex: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     insecure: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -26,7 +38,7 @@ pub struct NewProjectRequest {
 }
 
 impl NewProjectRequest {
-    pub fn new(name: String, homepage: String, backend: String) -> Self {
+    pub fn new(name: String, homepage: String, backend: Backend) -> Self {
         NewProjectRequest {
             name,
             homepage,
@@ -66,19 +78,28 @@ impl NewProjectRequest {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NewProject {
-    pub backend: String,
-    pub created_on: f64,
+    pub backend: Backend,
+    pub created_on: Timestamp,
     pub homepage: String,
-    pub id: u32,
+    pub id: ProjectId,
     pub name: String,
     pub regex: Option<String>,
-    pub updated_on: f64,
+    pub updated_on: Timestamp,
     pub version: Option<String>,
     pub version_url: Option<String>,
     pub versions: Vec<String>,
     pub stable_versions: Vec<String>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for NewProject {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
 }
 
 impl SingleRequest<NewProject, NewProject> for NewProjectRequest {
@@ -101,13 +122,35 @@ impl SingleRequest<NewProject, NewProject> for NewProjectRequest {
     fn extract(&self, page: NewProject) -> NewProject {
         page
     }
+
+    fn validate(&self) -> Result<(), Vec<Diagnostic>> {
+        let mut diagnostics = Diagnostics::new();
+
+        diagnostics.name(&self.name);
+        diagnostics.homepage(&self.homepage);
+        diagnostics.backend(&self.backend);
+        diagnostics.version_url(&self.backend, self.version_url.as_deref());
+        diagnostics.list("version_prefix", self.version_prefix.as_deref());
+        diagnostics.regex(self.regex.as_deref());
+
+        diagnostics.finish()
+    }
+
+    fn unknown_fields(&self, page: &NewProject) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
 
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ProjectQuery {
-    ecosystem: Option<String>,
+    ecosystem: Option<Ecosystem>,
     name: Option<String>,
     items_per_page: u32,
-    callback: Option<Box<dyn Fn(u32, u32)>>,
+
+    #[serde(skip)]
+    start_page: Option<u32>,
+    #[serde(skip)]
+    progress: Option<ProgressCallback>,
 }
 
 impl ProjectQuery {
@@ -116,7 +159,8 @@ impl ProjectQuery {
             ecosystem: None,
             name: None,
             items_per_page: DEFAULT_PER_PAGE,
-            callback: None,
+            start_page: None,
+            progress: None,
         }
     }
 }
@@ -127,6 +171,7 @@ impl Debug for ProjectQuery {
             .field("ecosystem", &self.ecosystem)
             .field("name", &self.name)
             .field("items_per_page", &self.items_per_page)
+            .field("start_page", &self.start_page)
             .finish()
     }
 }
@@ -138,7 +183,7 @@ impl Default for ProjectQuery {
 }
 
 impl ProjectQuery {
-    pub fn ecosystem(mut self, ecosystem: String) -> Self {
+    pub fn ecosystem(mut self, ecosystem: Ecosystem) -> Self {
         self.ecosystem = Some(ecosystem);
         self
     }
@@ -152,46 +197,80 @@ impl ProjectQuery {
         self.items_per_page = items_per_page.clamp(1, 250);
         self
     }
+
+    /// call the given function whenever the progress of this query changes
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&Progress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// publish progress updates for this query to a [`watch`] channel
+    pub fn progress_watch(mut self) -> (Self, watch::Receiver<Progress>) {
+        let (callback, receiver) = watch_callback();
+        self.progress = Some(callback);
+        (self, receiver)
+    }
 }
 
 #[derive(Debug, Serialize)]
-pub struct ProjectPageQuery<'a> {
+pub struct ProjectPageQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
-    ecosystem: Option<&'a String>,
+    ecosystem: Option<Ecosystem>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<&'a String>,
+    name: Option<String>,
 
     page: u32,
     items_per_page: u32,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ProjectPage {
-    items: Vec<Project>,
-
-    #[allow(unused)]
-    page: u32,
-    items_per_page: u32,
-    total_items: u32,
-}
+pub type ProjectPage = Page<Project>;
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Project {
-    pub backend: String,
-    pub created_on: f64,
-    pub ecosystem: String,
+    pub backend: Backend,
+    pub created_on: Timestamp,
+    pub ecosystem: Ecosystem,
     pub homepage: String,
-    pub id: u32,
+    pub id: ProjectId,
     pub name: String,
     pub regex: Option<String>,
-    pub updated_on: f64,
-    pub version: String,
+    pub updated_on: Timestamp,
+    pub version: Option<String>,
     pub version_url: Option<String>,
     pub versions: Vec<String>,
     pub stable_versions: Vec<String>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
 }
 
-impl<'a> SingleRequest<ProjectPage, Vec<Project>> for ProjectPageQuery<'a> {
+impl ExtraFields for Project {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
+}
+
+impl Project {
+    /// reference to this project by its ecosystem and name
+    pub fn reference(&self) -> ProjectRef {
+        ProjectRef::new(self.ecosystem.clone(), self.name.clone())
+    }
+
+    /// time that has passed since the project was created
+    pub fn age(&self) -> Duration {
+        self.created_on.elapsed()
+    }
+
+    /// check whether the project was last updated more than the given duration ago
+    pub fn last_checked_before(&self, duration: Duration) -> bool {
+        self.updated_on.elapsed() > duration
+    }
+}
+
+impl SingleRequest<ProjectPage, Vec<Project>> for ProjectPageQuery {
     fn method(&self) -> RequestMethod {
         RequestMethod::GET
     }
@@ -211,27 +290,56 @@ impl<'a> SingleRequest<ProjectPage, Vec<Project>> for ProjectPageQuery<'a> {
     fn extract(&self, page: ProjectPage) -> Vec<Project> {
         page.items
     }
+
+    fn unknown_fields(&self, page: &ProjectPage) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
 
-impl Pagination for ProjectPage {
-    fn pages(&self) -> u32 {
-        super::num_pages(self.total_items, self.items_per_page)
+impl ItemKey for Project {
+    type Key = ProjectId;
+
+    fn key(&self) -> Self::Key {
+        self.id
     }
 }
 
-impl<'a> PaginatedRequest<'a, ProjectPage, Vec<Project>, ProjectPageQuery<'a>> for ProjectQuery {
-    fn page_request(&'a self, page: u32) -> ProjectPageQuery<'a> {
+impl PaginatedRequest<ProjectPage, Vec<Project>, ProjectPageQuery> for ProjectQuery {
+    fn page_request(&self, page: u32) -> ProjectPageQuery {
         ProjectPageQuery {
-            ecosystem: self.ecosystem.as_ref(),
-            name: self.name.as_ref(),
+            ecosystem: self.ecosystem.clone(),
+            name: self.name.clone(),
             page,
             items_per_page: self.items_per_page,
         }
     }
 
-    fn callback(&'a self, page: u32, pages: u32) {
-        if let Some(ref callback) = &self.callback {
-            callback(page, pages)
+    fn report_progress(&self, progress: &Progress) {
+        if let Some(ref callback) = &self.progress {
+            callback(progress)
         }
     }
+
+    fn first_page(&self) -> u32 {
+        self.start_page.unwrap_or(1)
+    }
+
+    fn count_request(&self) -> ProjectPageQuery {
+        ProjectPageQuery {
+            items_per_page: 1,
+            ..self.page_request(1)
+        }
+    }
+}
+
+impl Resumable for ProjectQuery {
+    fn page_size(&self) -> u32 {
+        self.items_per_page
+    }
+
+    fn resume(mut self, last_page: u32, items_per_page: u32) -> Self {
+        self.items_per_page = items_per_page;
+        self.start_page = Some(last_page + 1);
+        self
+    }
 }
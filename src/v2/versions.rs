@@ -1,25 +1,65 @@
 use serde::{Deserialize, Serialize};
 
 use crate::errors::QueryError;
+use crate::fields::{Extra, ExtraFields};
 use crate::request::{RequestMethod, SingleRequest};
+use crate::types::{Backend, ProjectId, VersionScheme};
+use crate::validation::{Diagnostic, Diagnostics};
 
-#[derive(Debug, Default, Serialize)]
-pub struct ModifyProjectRequest {
-    // search parameters for existing projects
-    #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    homepage: Option<String>,
+/// existing project, identified either by its ID or by its name and homepage
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum ProjectSelector {
+    Id { id: ProjectId },
+    NameAndHomepage { name: String, homepage: String },
+}
+
+/// trigger a check for new versions of an existing project
+#[derive(Debug, Serialize)]
+pub struct CheckProjectRequest {
+    #[serde(flatten)]
+    project: ProjectSelector,
 
-    // parameters for creating a temporary project
     #[serde(skip_serializing_if = "Option::is_none")]
-    backend: Option<String>,
+    dry_run: Option<bool>,
+}
+
+impl CheckProjectRequest {
+    pub fn with_id(id: ProjectId) -> Self {
+        CheckProjectRequest {
+            project: ProjectSelector::Id { id },
+            dry_run: None,
+        }
+    }
+
+    pub fn with_name_and_homepage(name: String, homepage: String) -> Self {
+        CheckProjectRequest {
+            project: ProjectSelector::NameAndHomepage { name, homepage },
+            dry_run: None,
+        }
+    }
+
+    /// only report newly found versions, without saving them
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = Some(dry_run);
+        self
+    }
+}
+
+/// check for versions of a temporary project that is not saved
+///
+/// Requests for temporary projects are always sent as dry runs.
+#[derive(Debug, Serialize)]
+pub struct TemporaryProjectRequest {
+    name: String,
+    homepage: String,
+    backend: Backend,
+    dry_run: bool,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     version_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    version_scheme: Option<String>,
+    version_scheme: Option<VersionScheme>,
     #[serde(skip_serializing_if = "Option::is_none")]
     version_pattern: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -34,62 +74,34 @@ pub struct ModifyProjectRequest {
     insecure: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     releases_only: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    dry_run: Option<bool>,
 }
 
-impl ModifyProjectRequest {
-    pub fn with_id(id: u32) -> Self {
-        ModifyProjectRequest {
-            id: Some(id),
-            ..Default::default()
-        }
-    }
-
-    pub fn with_name(name: String) -> Self {
-        ModifyProjectRequest {
-            name: Some(name),
-            ..Default::default()
-        }
-    }
-
-    pub fn with_homepage(homepage: String) -> Self {
-        ModifyProjectRequest {
-            homepage: Some(homepage),
-            ..Default::default()
+impl TemporaryProjectRequest {
+    pub fn new(name: String, homepage: String, backend: Backend) -> Self {
+        TemporaryProjectRequest {
+            name,
+            homepage,
+            backend,
+            dry_run: true,
+
+            version_url: None,
+            version_scheme: None,
+            version_pattern: None,
+            version_prefix: None,
+            pre_release_filter: None,
+            version_filter: None,
+            regex: None,
+            insecure: None,
+            releases_only: None,
         }
     }
 
-    pub fn temporary() -> Self {
-        ModifyProjectRequest::default()
-    }
-
-    pub fn id(mut self, id: u32) -> Self {
-        self.id = Some(id);
-        self
-    }
-
-    pub fn name(mut self, name: String) -> Self {
-        self.name = Some(name);
-        self
-    }
-
-    pub fn homepage(mut self, homepage: String) -> Self {
-        self.homepage = Some(homepage);
-        self
-    }
-
-    pub fn backend(mut self, backend: String) -> Self {
-        self.backend = Some(backend);
-        self
-    }
-
     pub fn version_url(mut self, version_url: String) -> Self {
         self.version_url = Some(version_url);
         self
     }
 
-    pub fn version_scheme(mut self, version_scheme: String) -> Self {
+    pub fn version_scheme(mut self, version_scheme: VersionScheme) -> Self {
         self.version_scheme = Some(version_scheme);
         self
     }
@@ -128,22 +140,26 @@ impl ModifyProjectRequest {
         self.releases_only = Some(releases_only);
         self
     }
-
-    pub fn dry_run(mut self, dry_run: bool) -> Self {
-        self.dry_run = Some(dry_run);
-        self
-    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NewVersions {
     pub found_versions: Vec<String>,
-    pub latest_version: String,
+    pub latest_version: Option<String>,
     pub versions: Vec<String>,
     pub stable_versions: Vec<String>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for NewVersions {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
 }
 
-impl SingleRequest<NewVersions, NewVersions> for ModifyProjectRequest {
+impl SingleRequest<NewVersions, NewVersions> for CheckProjectRequest {
     fn method(&self) -> RequestMethod {
         RequestMethod::POST
     }
@@ -163,24 +179,90 @@ impl SingleRequest<NewVersions, NewVersions> for ModifyProjectRequest {
     fn extract(&self, page: NewVersions) -> NewVersions {
         page
     }
+
+    fn validate(&self) -> Result<(), Vec<Diagnostic>> {
+        let mut diagnostics = Diagnostics::new();
+
+        if let ProjectSelector::NameAndHomepage { name, homepage } = &self.project {
+            diagnostics.name(name);
+            diagnostics.homepage(homepage);
+        }
+
+        diagnostics.finish()
+    }
+
+    fn unknown_fields(&self, page: &NewVersions) -> Vec<String> {
+        page.unknown_fields()
+    }
+}
+
+impl SingleRequest<NewVersions, NewVersions> for TemporaryProjectRequest {
+    fn method(&self) -> RequestMethod {
+        RequestMethod::POST
+    }
+
+    fn path(&self) -> Result<String, QueryError> {
+        Ok(String::from("/api/v2/versions/"))
+    }
+
+    fn body(&self) -> Result<Option<String>, QueryError> {
+        Ok(Some(serde_json::to_string_pretty(self)?))
+    }
+
+    fn parse(&self, string: &str) -> Result<NewVersions, QueryError> {
+        Ok(serde_json::from_str(string)?)
+    }
+
+    fn extract(&self, page: NewVersions) -> NewVersions {
+        page
+    }
+
+    fn validate(&self) -> Result<(), Vec<Diagnostic>> {
+        let mut diagnostics = Diagnostics::new();
+
+        diagnostics.name(&self.name);
+        diagnostics.homepage(&self.homepage);
+        diagnostics.backend(&self.backend);
+        diagnostics.version_url(&self.backend, self.version_url.as_deref());
+        diagnostics.version_scheme(self.version_scheme.as_ref());
+        diagnostics.list("version_prefix", self.version_prefix.as_deref());
+        diagnostics.list("pre_release_filter", self.pre_release_filter.as_deref());
+        diagnostics.list("version_filter", self.version_filter.as_deref());
+        diagnostics.regex(self.regex.as_deref());
+
+        diagnostics.finish()
+    }
+
+    fn unknown_fields(&self, page: &NewVersions) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct VersionQuery {
-    project_id: u32,
+    project_id: ProjectId,
 }
 
 impl VersionQuery {
-    pub fn new(project_id: u32) -> Self {
+    pub fn new(project_id: ProjectId) -> Self {
         VersionQuery { project_id }
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Versions {
-    pub latest_version: String,
+    pub latest_version: Option<String>,
     pub versions: Vec<String>,
     pub stable_versions: Vec<String>,
+
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl ExtraFields for Versions {
+    fn unknown_fields(&self) -> Vec<String> {
+        self.extra.keys().cloned().collect()
+    }
 }
 
 impl SingleRequest<Versions, Versions> for VersionQuery {
@@ -203,4 +285,8 @@ impl SingleRequest<Versions, Versions> for VersionQuery {
     fn extract(&self, page: Versions) -> Versions {
         page
     }
+
+    fn unknown_fields(&self, page: &Versions) -> Vec<String> {
+        page.unknown_fields()
+    }
 }
@@ -0,0 +1,192 @@
+use std::fmt::{Display, Formatter};
+
+use url::Url;
+
+use crate::types::{Backend, VersionScheme};
+
+/// problem with a single field of a request
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// helper for collecting all problems with a request
+#[derive(Debug, Default)]
+pub(crate) struct Diagnostics {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, field: &'static str, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            field,
+            message: message.into(),
+        });
+    }
+
+    pub(crate) fn name(&mut self, name: &str) {
+        if name.trim().is_empty() {
+            self.push("name", "Project name must not be empty.");
+        }
+    }
+
+    pub(crate) fn homepage(&mut self, homepage: &str) {
+        if let Err(message) = check_http_url(homepage) {
+            self.push("homepage", message);
+        }
+    }
+
+    pub(crate) fn backend(&mut self, backend: &Backend) {
+        if let Backend::Other(other) = backend {
+            self.push("backend", format!("Unknown backend: {:?}", other));
+        }
+    }
+
+    /// check the version URL against the rules of the backend that uses it
+    ///
+    /// The GitHub backend cannot check a project for new versions without a version URL, so it
+    /// is required for this backend.
+    pub(crate) fn version_url(&mut self, backend: &Backend, version_url: Option<&str>) {
+        let version_url = match (backend, version_url) {
+            (_, Some(version_url)) => version_url,
+            (Backend::GitHub, None) => {
+                self.push("version_url", "Version URL is required for the GitHub backend");
+                return;
+            },
+            (_, None) => return,
+        };
+
+        match backend {
+            Backend::GitHub => {
+                let segments: Vec<&str> = version_url.split('/').collect();
+                if segments.len() != 2 || segments.iter().any(|segment| segment.trim().is_empty()) {
+                    self.push(
+                        "version_url",
+                        format!(
+                            "Version URL for the GitHub backend must have the form \"owner/repo\": {:?}",
+                            version_url
+                        ),
+                    );
+                }
+            },
+            Backend::GitLab | Backend::Custom => {
+                if let Err(message) = check_http_url(version_url) {
+                    self.push("version_url", message);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    pub(crate) fn regex(&mut self, regex: Option<&str>) {
+        if let Some(regex) = regex {
+            if let Err(error) = fancy_regex::Regex::new(regex) {
+                self.push("regex", format!("Invalid regular expression: {}", error));
+            }
+        }
+    }
+
+    pub(crate) fn version_scheme(&mut self, version_scheme: Option<&VersionScheme>) {
+        if let Some(VersionScheme::Other(other)) = version_scheme {
+            self.push("version_scheme", format!("Unknown version scheme: {:?}", other));
+        }
+    }
+
+    /// check a list of values that is separated by semicolons (for example, version prefixes)
+    pub(crate) fn list(&mut self, field: &'static str, list: Option<&str>) {
+        if let Some(list) = list {
+            if list.split(';').any(|entry| entry.is_empty() || entry.trim() != entry) {
+                self.push(
+                    field,
+                    format!(
+                        "List must be separated by single semicolons, without empty entries or whitespace: {:?}",
+                        list
+                    ),
+                );
+            }
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), Vec<Diagnostic>> {
+        if self.diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(self.diagnostics)
+        }
+    }
+}
+
+fn check_http_url(url: &str) -> Result<(), String> {
+    match Url::parse(url) {
+        Ok(parsed) if (parsed.scheme() == "http" || parsed.scheme() == "https") && parsed.host().is_some() => Ok(()),
+        Ok(_) => Err(format!("URL must be an http(s) URL: {:?}", url)),
+        Err(error) => Err(format!("Invalid URL {:?}: {}", url, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(diagnostics: Diagnostics) -> Vec<&'static str> {
+        match diagnostics.finish() {
+            Ok(()) => Vec::new(),
+            Err(diagnostics) => diagnostics.into_iter().map(|diagnostic| diagnostic.field).collect(),
+        }
+    }
+
+    #[test]
+    fn version_urls() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.version_url(&Backend::GitHub, Some("fedora-infra/anitya"));
+        diagnostics.version_url(&Backend::GitLab, Some("https://gitlab.com/fdroid/fdroidserver"));
+        diagnostics.version_url(&Backend::PyPI, Some("anything"));
+        diagnostics.version_url(&Backend::PyPI, None);
+        assert!(fields(diagnostics).is_empty());
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.version_url(&Backend::GitHub, Some("https://github.com/fedora-infra/anitya"));
+        diagnostics.version_url(&Backend::GitHub, Some("fedora-infra/"));
+        diagnostics.version_url(&Backend::Custom, Some("example.org/releases"));
+        diagnostics.version_url(&Backend::GitHub, None);
+        assert_eq!(fields(diagnostics), vec!["version_url"; 4]);
+    }
+
+    #[test]
+    fn regexes() {
+        let mut diagnostics = Diagnostics::new();
+        // regexes use the Python syntax, including lookarounds
+        diagnostics.regex(Some(r"anitya-(?!rc)([\d.]+)\.tar\.gz"));
+        assert!(fields(diagnostics).is_empty());
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.regex(Some(r"anitya-([\d.]+\.tar\.gz"));
+        assert_eq!(fields(diagnostics), vec!["regex"]);
+    }
+
+    #[test]
+    fn lists() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.list("version_prefix", Some("v"));
+        diagnostics.list("version_prefix", Some("v;release-"));
+        assert!(fields(diagnostics).is_empty());
+
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.list("version_prefix", Some("v;"));
+        diagnostics.list("version_prefix", Some(";v"));
+        diagnostics.list("version_prefix", Some("v;;release-"));
+        diagnostics.list("version_prefix", Some("v; release-"));
+        assert_eq!(fields(diagnostics), vec!["version_prefix"; 4]);
+    }
+}
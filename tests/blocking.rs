@@ -0,0 +1,19 @@
+#![cfg(feature = "blocking")]
+
+use std::time::Duration;
+
+use anitya::v2::VersionQuery;
+use anitya::ProjectId;
+
+mod common;
+
+#[test]
+fn blocking_version_query() {
+    let client = common::builder("version_query")
+        .with_delay(Duration::from_millis(1000))
+        .build_blocking()
+        .unwrap();
+
+    let query = VersionQuery::new(ProjectId(7635));
+    let _result = client.request(&query).unwrap();
+}
@@ -0,0 +1,128 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use anitya::cache::{Cache, CacheMode};
+use anitya::transport::{HttpRequest, HttpResponse, Transport};
+use anitya::v2::VersionQuery;
+use anitya::{ClientBuilder, ProjectId, QueryError};
+
+const VERSIONS: &str = r#"{"latest_version": "1.0.0", "versions": ["1.0.0"], "stable_versions": ["1.0.0"]}"#;
+
+/// in-memory transport that optionally sends an `ETag` and honors `If-None-Match`
+#[derive(Debug)]
+struct ValidatingTransport {
+    etag: Option<&'static str>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl ValidatingTransport {
+    fn new(etag: Option<&'static str>) -> Arc<Self> {
+        Arc::new(ValidatingTransport {
+            etag,
+            requests: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for ValidatingTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        let not_modified = self.etag.is_some() && request.header("If-None-Match") == self.etag;
+        self.requests.lock().unwrap().push(request);
+
+        let response = match (not_modified, self.etag) {
+            (true, Some(etag)) => HttpResponse {
+                status: 304,
+                headers: vec![(String::from("ETag"), etag.to_string())],
+                body: String::new(),
+            },
+            (_, Some(etag)) => HttpResponse {
+                status: 200,
+                headers: vec![(String::from("ETag"), etag.to_string())],
+                body: VERSIONS.to_string(),
+            },
+            (_, None) => HttpResponse {
+                status: 200,
+                headers: Vec::new(),
+                body: VERSIONS.to_string(),
+            },
+        };
+
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+#[tokio::test]
+async fn etag_revalidation() {
+    let transport = ValidatingTransport::new(Some("\"v1\""));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_cache(Cache::memory())
+        .build()
+        .unwrap();
+
+    let query = VersionQuery::new(ProjectId(7635));
+
+    let first = client.request(&query).await.unwrap();
+    let second = client.request(&query).await.unwrap();
+    assert_eq!(first.latest_version, second.latest_version);
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(requests[1].header("If-None-Match"), Some("\"v1\""));
+}
+
+#[tokio::test]
+async fn ttl_without_validators() {
+    let transport = ValidatingTransport::new(None);
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_cache(Cache::memory().ttl(Duration::from_secs(60)))
+        .build()
+        .unwrap();
+
+    let query = VersionQuery::new(ProjectId(7635));
+
+    client.request(&query).await.unwrap();
+    client.request(&query).await.unwrap();
+    assert_eq!(transport.requests().len(), 1);
+
+    // bypassing the cache always results in a request
+    client.cache_mode(CacheMode::Bypass).request(&query).await.unwrap();
+    assert_eq!(transport.requests().len(), 2);
+
+    // refreshing the cache always results in a request
+    client.cache_mode(CacheMode::Refresh).request(&query).await.unwrap();
+    assert_eq!(transport.requests().len(), 3);
+
+    client.request(&query).await.unwrap();
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn refresh_skips_validators() {
+    let transport = ValidatingTransport::new(Some("\"v1\""));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_cache(Cache::memory())
+        .build()
+        .unwrap();
+
+    let query = VersionQuery::new(ProjectId(7635));
+
+    client.request(&query).await.unwrap();
+    client.cache_mode(CacheMode::Refresh).request(&query).await.unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].header("If-None-Match"), None);
+}
@@ -0,0 +1,95 @@
+use futures::future::BoxFuture;
+
+use anitya::cassette::{CassetteMode, RecordingTransport, ReplayTransport};
+use anitya::transport::{HttpRequest, HttpResponse, Transport};
+use anitya::v2::{CheckProjectRequest, VersionQuery};
+use anitya::{ClientBuildError, ClientBuilder, ProjectId, QueryError};
+
+const VERSIONS: &str = r#"{"latest_version": "1.0.0", "versions": ["1.0.0"], "stable_versions": ["1.0.0"]}"#;
+const NEW_VERSIONS: &str =
+    r#"{"found_versions": [], "latest_version": "1.0.0", "versions": ["1.0.0"], "stable_versions": ["1.0.0"]}"#;
+
+/// in-memory transport that answers every request successfully
+#[derive(Debug)]
+struct EchoTransport;
+
+impl Transport for EchoTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        let body = if request.url.path() == "/api/v2/versions/" && request.body.is_some() {
+            NEW_VERSIONS
+        } else {
+            VERSIONS
+        };
+
+        Box::pin(async move {
+            Ok(HttpResponse {
+                status: 200,
+                headers: vec![(String::from("content-type"), String::from("application/json"))],
+                body: body.to_string(),
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let path = std::env::temp_dir().join(format!("anitya-cassette-{}.json", std::process::id()));
+
+    // record interactions with an authenticated client
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_token("SECRET")
+        .with_transport(RecordingTransport::new(EchoTransport, &path).redact("User-Agent"))
+        .build()
+        .unwrap();
+
+    client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    client
+        .request(&CheckProjectRequest::with_id(ProjectId(7635)))
+        .await
+        .unwrap();
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert!(!contents.contains("SECRET"));
+    assert!(!contents.contains("anitya-rs"));
+
+    // replay interactions without network access
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_token("SECRET")
+        .with_cassette(&path, CassetteMode::Replay)
+        .build()
+        .unwrap();
+
+    let versions = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(versions.latest_version.as_deref(), Some("1.0.0"));
+
+    let new_versions = client
+        .request(&CheckProjectRequest::with_id(ProjectId(7635)))
+        .await
+        .unwrap();
+    assert!(new_versions.found_versions.is_empty());
+
+    // every interaction is only replayed once
+    let error = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap_err();
+    assert!(matches!(error, QueryError::Transport { .. }));
+
+    // requests that were never recorded are rejected
+    let error = client.request(&VersionQuery::new(ProjectId(1))).await.unwrap_err();
+    assert!(matches!(error, QueryError::Transport { .. }));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn missing_cassette() {
+    assert!(ReplayTransport::new("/nonexistent/cassette.json").is_err());
+}
+
+#[test]
+fn replay_with_transport() {
+    // a custom transport would be ignored when replaying, so the combination is rejected
+    let result = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(EchoTransport)
+        .with_cassette("/nonexistent/cassette.json", CassetteMode::Replay)
+        .build();
+    assert!(matches!(result, Err(ClientBuildError::ReplayWithTransport)));
+}
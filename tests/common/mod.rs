@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+use anitya::cassette::CassetteMode;
+use anitya::ClientBuilder;
+
+/// client builder for tests that replays interactions from `tests/cassettes/<name>.json`
+///
+/// The cassettes are hand-written fixtures, not recordings of release-monitoring.org, and tests
+/// assert on their exact contents (item counts, totals, timestamps). They are always replayed,
+/// and must be edited by hand when new interactions are needed.
+pub fn builder(name: &str) -> ClientBuilder<'static> {
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "cassettes",
+        &format!("{name}.json"),
+    ]
+    .iter()
+    .collect();
+
+    ClientBuilder::new("https://release-monitoring.org").with_cassette(path, CassetteMode::Replay)
+}
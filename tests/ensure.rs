@@ -0,0 +1,97 @@
+use anitya::v2::{NewPackageRequest, NewProjectRequest};
+use anitya::{Backend, Difference, Ecosystem, ProjectId, ProjectRef};
+
+mod common;
+
+#[tokio::test]
+async fn ensure_existing_project() {
+    let client = common::builder("ensure").with_token("token").build().unwrap();
+
+    let request = NewProjectRequest::new(
+        String::from("requests"),
+        String::from("https://github.com/psf/requests"),
+        Backend::PyPI,
+    );
+    let ensured = client.ensure_project(&request).await.unwrap();
+
+    assert!(!ensured.created);
+    assert_eq!(ensured.value.id, ProjectId(4004));
+    assert_eq!(
+        ensured.differences,
+        vec![Difference {
+            field: "homepage",
+            requested: Some(String::from("https://github.com/psf/requests")),
+            existing: Some(String::from("https://pypi.org/project/requests")),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn ensure_new_project() {
+    let client = common::builder("ensure").with_token("token").build().unwrap();
+
+    let request = NewProjectRequest::new(
+        String::from("anitya-rs"),
+        String::from("https://github.com/example/anitya-rs"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("example/anitya-rs"));
+    let ensured = client.ensure_project(&request).await.unwrap();
+
+    assert!(ensured.created);
+    assert_eq!(ensured.value.id, ProjectId(390001));
+    assert_eq!(
+        ensured.value.ecosystem,
+        Ecosystem::from("https://github.com/example/anitya-rs")
+    );
+    assert!(ensured.differences.is_empty());
+}
+
+#[tokio::test]
+async fn ensure_existing_github_project() {
+    let client = common::builder("ensure").with_token("token").build().unwrap();
+
+    // neither the ecosystem nor the homepage match, but the GitHub repository does
+    let request = NewProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://release-monitoring.org"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("fedora-infra/anitya"));
+    let ensured = client.ensure_project(&request).await.unwrap();
+
+    assert!(!ensured.created);
+    assert_eq!(ensured.value.id, ProjectId(6035));
+    assert_eq!(
+        ensured.differences,
+        vec![
+            Difference {
+                field: "ecosystem",
+                requested: Some(String::from("https://release-monitoring.org")),
+                existing: Some(String::from("https://github.com/fedora-infra/anitya")),
+            },
+            Difference {
+                field: "homepage",
+                requested: Some(String::from("https://release-monitoring.org")),
+                existing: Some(String::from("https://github.com/fedora-infra/anitya")),
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn ensure_package_conflict() {
+    let client = common::builder("ensure").with_token("token").build().unwrap();
+
+    // the package mapping is created by someone else between the lookup and the creation
+    let request = NewPackageRequest::new(
+        String::from("Fedora"),
+        String::from("python-requests"),
+        ProjectRef::new(Ecosystem::PyPI, String::from("requests")),
+    );
+    let ensured = client.ensure_package(&request).await.unwrap();
+
+    assert!(!ensured.created);
+    assert_eq!(ensured.value.version.as_deref(), Some("2.32.3"));
+    assert!(ensured.differences.is_empty());
+}
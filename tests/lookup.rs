@@ -0,0 +1,81 @@
+use anitya::v2::Package;
+use anitya::{Ecosystem, ProjectId, ProjectRef, QueryError};
+
+mod common;
+
+#[tokio::test]
+async fn project_by_id() {
+    let client = common::builder("lookup").build().unwrap();
+
+    let project = client.get_project(ProjectId(4004)).await.unwrap().unwrap();
+    assert_eq!(project.name, "requests");
+    assert_eq!(project.ecosystem, Ecosystem::PyPI);
+
+    assert!(client.get_project(ProjectId(99999999)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn project_by_id_without_timestamps() {
+    let client = common::builder("lookup").build().unwrap();
+
+    // API v1 responses without timestamps cannot be converted into the API v2 model
+    let error = client.get_project(ProjectId(4005)).await.unwrap_err();
+    assert!(matches!(error, QueryError::MissingField { field: "created_on" }));
+}
+
+#[tokio::test]
+async fn project_by_ecosystem() {
+    let client = common::builder("lookup").build().unwrap();
+
+    let project = client
+        .get_project_by_ecosystem(&Ecosystem::PyPI, "requests")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(project.id, ProjectId(4004));
+
+    let project = client
+        .get_project_by_ecosystem(&Ecosystem::PyPI, "no-such-project")
+        .await
+        .unwrap();
+    assert!(project.is_none());
+}
+
+#[tokio::test]
+async fn project_for_package() {
+    let client = common::builder("lookup").build().unwrap();
+
+    let project = client
+        .get_project_for_package("Fedora", "python-requests")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(project.id, ProjectId(4004));
+
+    let project = client
+        .get_project_for_package("Fedora", "no-such-package")
+        .await
+        .unwrap();
+    assert!(project.is_none());
+}
+
+#[tokio::test]
+async fn resolve_package() {
+    let client = common::builder("lookup").build().unwrap();
+
+    let package: Package = serde_json::from_str(
+        r#"{"distribution": "Fedora", "name": "python-requests", "project": "requests", "ecosystem": "pypi", "version": "2.32.3"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        package.project_ref(),
+        ProjectRef::new(Ecosystem::PyPI, String::from("requests"))
+    );
+
+    let project = client.resolve_package(&package).await.unwrap().unwrap();
+    assert_eq!(project.id, ProjectId(4004));
+    assert_eq!(project.reference(), package.project_ref());
+
+    let versions = client.resolve_package_versions(&package).await.unwrap().unwrap();
+    assert_eq!(versions.latest_version.as_deref(), Some("2.32.3"));
+}
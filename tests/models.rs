@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use anitya::{v1, v2, ExtraFields};
+
+/// response body of the n-th interaction in the cassette `tests/cassettes/<name>.json`
+fn response_body(name: &str, n: usize) -> String {
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "tests",
+        "cassettes",
+        &format!("{name}.json"),
+    ]
+    .iter()
+    .collect();
+
+    let cassette: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    cassette["interactions"][n]["response"]["body"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+/// check that serializing a model and deserializing it again results in an identical value
+fn round_trip<T>(body: &str)
+where
+    T: Debug + DeserializeOwned + PartialEq + Serialize,
+{
+    let value: T = serde_json::from_str(body).unwrap();
+
+    let json = serde_json::to_string(&value).unwrap();
+    let again: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(value, again);
+
+    // serialized output is stable
+    assert_eq!(json, serde_json::to_string(&again).unwrap());
+}
+
+#[test]
+fn v2_models() {
+    round_trip::<v2::ProjectPage>(&response_body("project_query", 0));
+    round_trip::<v2::PackagePage>(&response_body("package_query", 0));
+    round_trip::<v2::Versions>(&response_body("version_query", 0));
+
+    round_trip::<v2::NewProject>(
+        r#"{
+            "backend": "crates.io",
+            "created_on": 1660125303.473217,
+            "homepage": "https://crates.io/crates/anitya",
+            "id": 302145,
+            "name": "anitya",
+            "regex": null,
+            "updated_on": 1660125303.473223,
+            "version": null,
+            "version_url": null,
+            "versions": [],
+            "stable_versions": []
+        }"#,
+    );
+    round_trip::<v2::NewPackage>(r#"{"distribution": "Fedora", "name": "rust-anitya"}"#);
+    round_trip::<v2::NewVersions>(
+        r#"{
+            "found_versions": ["0.1.0"],
+            "latest_version": "0.1.0",
+            "versions": ["0.1.0"],
+            "stable_versions": ["0.1.0"]
+        }"#,
+    );
+}
+
+#[test]
+fn v1_models() {
+    round_trip::<v1::Projects>(&response_body("v1_projects", 0));
+    round_trip::<v1::Project>(&response_body("v1_project", 0));
+    round_trip::<v1::ProjectNames>(&response_body("v1_names", 0));
+    round_trip::<v1::DistroNames>(&response_body("v1_names", 1));
+}
+
+#[test]
+fn schema_drift() {
+    // projects without any versions have no latest version, and unknown fields are kept
+    let body = r#"{
+        "items": [{
+            "backend": "GitHub",
+            "created_on": 1660125303.473217,
+            "ecosystem": "https://github.com/example/new",
+            "homepage": "https://github.com/example/new",
+            "id": 302146,
+            "name": "new",
+            "regex": null,
+            "updated_on": 1660125303.473223,
+            "version": null,
+            "version_url": "example/new",
+            "versions": [],
+            "stable_versions": [],
+            "archived": false
+        }],
+        "page": 1,
+        "items_per_page": 25,
+        "total_items": 1,
+        "next_page": null
+    }"#;
+
+    let page: v2::ProjectPage = serde_json::from_str(body).unwrap();
+    assert_eq!(page.items[0].version, None);
+    assert_eq!(page.items[0].extra["archived"], serde_json::json!(false));
+    assert_eq!(page.unknown_fields(), vec!["next_page", "items[0].archived"]);
+
+    round_trip::<v2::ProjectPage>(body);
+}
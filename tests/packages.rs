@@ -1,13 +1,14 @@
 use std::time::Duration;
 
 use anitya::v2::PackageQuery;
-use anitya::ClientBuilder;
+
+mod common;
 
 #[tokio::test]
 async fn package_query() {
     env_logger::builder().filter_level(log::LevelFilter::Debug).init();
 
-    let client = ClientBuilder::new("https://release-monitoring.org")
+    let client = common::builder("package_query")
         .with_delay(Duration::from_millis(1000))
         .build()
         .unwrap();
@@ -15,3 +16,19 @@ async fn package_query() {
     let query = PackageQuery::new();
     let _result = client.paginated_request(&query).await.unwrap();
 }
+
+#[tokio::test]
+async fn package_query_concurrent() {
+    let client = common::builder("package_query_concurrent")
+        .with_delay(Duration::from_millis(250))
+        .max_concurrent_pages(4)
+        .build()
+        .unwrap();
+
+    let query = PackageQuery::new()
+        .distribution(String::from("Fedora"))
+        .items_per_page(5);
+    let result = client.paginated_request(&query).await.unwrap();
+
+    assert_eq!(result.len(), 12);
+}
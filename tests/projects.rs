@@ -1,13 +1,18 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use anitya::v2::ProjectQuery;
-use anitya::ClientBuilder;
+use futures::{StreamExt, TryStreamExt};
+
+use anitya::v2::{Project, ProjectQuery};
+use anitya::{AnityaClient, Checkpoint, ConsistencyMode, Ecosystem, Pagination, QueryError};
+
+mod common;
 
 #[tokio::test]
 async fn project_query() {
     env_logger::builder().filter_level(log::LevelFilter::Debug).init();
 
-    let client = ClientBuilder::new("https://release-monitoring.org")
+    let client = common::builder("project_query")
         .with_delay(Duration::from_millis(1000))
         .build()
         .unwrap();
@@ -15,3 +20,185 @@ async fn project_query() {
     let query = ProjectQuery::new();
     let _result = client.paginated_request(&query).await.unwrap();
 }
+
+#[tokio::test]
+async fn project_stream() {
+    let client = common::builder("project_stream")
+        .with_delay(Duration::from_millis(1000))
+        .build()
+        .unwrap();
+
+    // only the first two pages need to be fetched for this
+    let query = ProjectQuery::new().items_per_page(10);
+    let result: Vec<_> = client.paginated_stream(&query).take(15).try_collect().await.unwrap();
+
+    assert_eq!(result.len(), 15);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn project_query_spawned() {
+    let client = common::builder("project_query").build().unwrap();
+
+    // both the client and the query are moved into the spawned task
+    let query = ProjectQuery::new();
+    let handle = tokio::spawn(async move { client.paginated_request(&query).await });
+
+    let result = handle.await.unwrap().unwrap();
+    assert_eq!(result.len(), 27);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn project_stream_spawned() {
+    let client = common::builder("project_stream").build().unwrap();
+
+    let query = ProjectQuery::new().items_per_page(10);
+    let handle = tokio::spawn(async move { client.paginated_stream(&query).take(15).try_collect::<Vec<_>>().await });
+
+    let result = handle.await.unwrap().unwrap();
+    assert_eq!(result.len(), 15);
+}
+
+#[tokio::test]
+async fn project_query_progress() {
+    let client = common::builder("project_query").build().unwrap();
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let recorded = reports.clone();
+
+    let query = ProjectQuery::new().on_progress(move |progress| recorded.lock().unwrap().push(progress.clone()));
+    client.paginated_request(&query).await.unwrap();
+
+    let reports = reports.lock().unwrap();
+    let summary: Vec<_> = reports
+        .iter()
+        .map(|progress| (progress.pages_done, progress.total_pages, progress.items_fetched))
+        .collect();
+    assert_eq!(summary, vec![(0, None, 0), (1, Some(2), 25), (2, Some(2), 27)]);
+    assert_eq!(reports.last().unwrap().eta, Some(Duration::ZERO));
+}
+
+#[tokio::test]
+async fn project_query_resume_error() {
+    let client = common::builder("project_query_resume").build().unwrap();
+
+    // partial results are discarded when the error is propagated with `?`
+    async fn fetch(client: &AnityaClient) -> Result<Vec<Project>, QueryError> {
+        Ok(client.resumable_request(&ProjectQuery::new()).await?)
+    }
+
+    let error = fetch(&client).await.unwrap_err();
+    assert!(matches!(error, QueryError::ServerError { .. }));
+}
+
+#[tokio::test]
+async fn project_query_resume() {
+    let client = common::builder("project_query_resume").build().unwrap();
+
+    // the second page fails with an HTTP 503 error on the first attempt
+    let query = ProjectQuery::new();
+    let partial = client.resumable_request(&query).await.unwrap_err();
+
+    assert_eq!(partial.items.len(), 25);
+    assert_eq!(partial.checkpoint.last_page, 1);
+    assert_eq!(partial.checkpoint.items_per_page, 25);
+    assert!(matches!(partial.error, QueryError::ServerError { .. }));
+
+    // checkpoints can be persisted and restored
+    let json = serde_json::to_string(&partial.checkpoint).unwrap();
+    let checkpoint: Checkpoint<ProjectQuery> = serde_json::from_str(&json).unwrap();
+
+    let query = checkpoint.resume();
+    let rest = client.resumable_request(&query).await.unwrap();
+    assert_eq!(rest.len(), 2);
+}
+
+#[tokio::test]
+async fn project_query_consistency() {
+    let client = common::builder("project_query_consistency").build().unwrap();
+
+    // two projects from the first page are removed before the second page is fetched
+    let query = ProjectQuery::new().items_per_page(10);
+    let result = client
+        .consistent_request(&query, ConsistencyMode::Refetch)
+        .await
+        .unwrap();
+
+    assert_eq!(result.items.len(), 25);
+    assert_eq!(result.duplicates, 8);
+    assert_eq!(result.drift, -2);
+    assert_eq!(result.possibly_missed, 0);
+}
+
+#[tokio::test]
+async fn project_query_refetch() {
+    let client = common::builder("project_query_refetch").build().unwrap();
+
+    // seven projects from the first two pages are removed before the third page is fetched
+    let query = ProjectQuery::new().items_per_page(5);
+    let result = client
+        .consistent_request(&query, ConsistencyMode::Refetch)
+        .await
+        .unwrap();
+
+    assert_eq!(result.items.len(), 25);
+    assert_eq!(result.duplicates, 3);
+    assert_eq!(result.drift, -7);
+    assert_eq!(result.possibly_missed, 0);
+}
+
+#[tokio::test]
+async fn project_query_duplicates() {
+    let client = common::builder("project_query_duplicates").build().unwrap();
+
+    // a new project is added before the second page is fetched
+    let query = ProjectQuery::new().items_per_page(10);
+    let result = client
+        .consistent_request(&query, ConsistencyMode::Report)
+        .await
+        .unwrap();
+
+    assert_eq!(result.items.len(), 25);
+    assert_eq!(result.duplicates, 1);
+    assert_eq!(result.drift, 1);
+    assert_eq!(result.possibly_missed, 0);
+}
+
+#[tokio::test]
+async fn project_page() {
+    let client = common::builder("project_page").build().unwrap();
+
+    let query = ProjectQuery::new().items_per_page(10);
+    let page = client.page(&query, 3).await.unwrap();
+
+    assert_eq!(page.items.len(), 7);
+    assert_eq!(page.page, 3);
+    assert_eq!(page.items_per_page, 10);
+    assert_eq!(page.total_items, 27);
+    assert_eq!(page.pages(), 3);
+}
+
+#[tokio::test]
+async fn project_count() {
+    let client = common::builder("project_count").build().unwrap();
+
+    // only a single page with a single item is fetched
+    let query = ProjectQuery::new().ecosystem(Ecosystem::CratesIo);
+    assert_eq!(client.count(&query).await.unwrap(), 151234);
+}
+
+#[tokio::test]
+async fn project_timestamps() {
+    let client = common::builder("project_page").build().unwrap();
+
+    let query = ProjectQuery::new().items_per_page(10);
+    let page = client.page(&query, 3).await.unwrap();
+
+    let day = Duration::from_secs(24 * 60 * 60);
+
+    // all projects in the fixture were created and updated years ago
+    for project in page.items {
+        assert!(project.created_on < project.updated_on);
+        assert!(project.age() > 365 * day);
+        assert!(project.last_checked_before(day));
+    }
+}
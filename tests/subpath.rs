@@ -0,0 +1,96 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use anitya::v2::VersionQuery;
+use anitya::{ClientBuilder, ProjectId, QueryError};
+
+const VERSIONS: &str = r#"{"latest_version": "1.0.0", "versions": ["1.0.0"], "stable_versions": ["1.0.0"]}"#;
+
+/// minimal HTTP server that only serves the versions endpoint under the given prefix
+///
+/// Returns the port the server is listening on, and the list of requested paths.
+async fn serve(prefix: &'static str) -> (u16, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let paths = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = paths.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = socket.read(&mut chunk).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                buffer.extend_from_slice(&chunk[..read]);
+            }
+
+            let head = String::from_utf8_lossy(&buffer);
+            let path = head.split_whitespace().nth(1).unwrap_or_default().to_string();
+            recorded.lock().unwrap().push(path.clone());
+
+            let (status, body) = if path.starts_with(&format!("{prefix}api/v2/versions/")) {
+                ("200 OK", VERSIONS)
+            } else {
+                ("404 Not Found", r#"{"error": "Not Found"}"#)
+            };
+
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    (port, paths)
+}
+
+#[tokio::test]
+async fn root_deployment() {
+    let (port, paths) = serve("/").await;
+
+    let url = format!("http://127.0.0.1:{port}");
+    let client = ClientBuilder::new(&url).build().unwrap();
+
+    client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(*paths.lock().unwrap(), vec!["/api/v2/versions/?project_id=7635"]);
+}
+
+#[tokio::test]
+async fn subpath_deployment() {
+    let (port, paths) = serve("/anitya/").await;
+
+    for url in [
+        format!("http://127.0.0.1:{port}/anitya/"),
+        format!("http://127.0.0.1:{port}/anitya"),
+    ] {
+        let client = ClientBuilder::new(&url).build().unwrap();
+        client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    }
+
+    assert_eq!(
+        *paths.lock().unwrap(),
+        vec![
+            "/anitya/api/v2/versions/?project_id=7635",
+            "/anitya/api/v2/versions/?project_id=7635"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn subpath_not_found() {
+    let (port, _paths) = serve("/anitya/").await;
+
+    let url = format!("http://127.0.0.1:{port}/other/");
+    let client = ClientBuilder::new(&url).build().unwrap();
+
+    let error = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap_err();
+    assert!(matches!(error, QueryError::NotFound { .. }));
+}
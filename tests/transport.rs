@@ -0,0 +1,165 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use anitya::transport::{HttpRequest, HttpResponse, Transport};
+use anitya::v2::VersionQuery;
+use anitya::{ClientBuilder, ProjectId, QueryError, RequestMethod, RetryPolicy};
+
+const VERSIONS: &str = r#"{"latest_version": "1.0.0", "versions": ["1.0.0", "0.9.0"], "stable_versions": ["1.0.0"]}"#;
+
+/// in-memory transport that returns canned responses in order
+#[derive(Debug, Default)]
+struct FakeTransport {
+    responses: Mutex<Vec<Result<HttpResponse, QueryError>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl FakeTransport {
+    fn new(responses: Vec<HttpResponse>) -> Self {
+        Self::with_results(responses.into_iter().map(Ok).collect())
+    }
+
+    fn with_results(mut responses: Vec<Result<HttpResponse, QueryError>>) -> Self {
+        responses.reverse();
+        FakeTransport {
+            responses: Mutex::new(responses),
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        self.requests.lock().unwrap().push(request);
+        let response = self.responses.lock().unwrap().pop().expect("unexpected request");
+        Box::pin(async move { response })
+    }
+}
+
+fn response(status: u16, headers: &[(&str, &str)], body: &str) -> HttpResponse {
+    HttpResponse {
+        status,
+        headers: headers
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        body: body.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn fake_transport() {
+    let transport = Arc::new(FakeTransport::new(vec![response(200, &[], VERSIONS)]));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let versions = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(versions.latest_version.as_deref(), Some("1.0.0"));
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, RequestMethod::GET);
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://release-monitoring.org/api/v2/versions/?project_id=7635"
+    );
+    assert_eq!(requests[0].header("accept"), Some("application/json"));
+    assert_eq!(requests[0].header("authorization"), None);
+}
+
+#[tokio::test]
+async fn retry_with_fake_transport() {
+    let transport = Arc::new(FakeTransport::new(vec![
+        response(503, &[("Retry-After", "0")], "Service Unavailable"),
+        response(502, &[("Retry-After", "0")], "Bad Gateway"),
+        response(200, &[], VERSIONS),
+    ]));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_retry(RetryPolicy::new().max_attempts(3))
+        .build()
+        .unwrap();
+
+    let versions = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(versions.latest_version.as_deref(), Some("1.0.0"));
+    assert_eq!(transport.requests.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn no_retry_with_fake_transport() {
+    let transport = Arc::new(FakeTransport::new(vec![response(503, &[], "Service Unavailable")]));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .build()
+        .unwrap();
+
+    let error = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap_err();
+    assert!(matches!(error, QueryError::ServerError { .. }));
+    assert_eq!(error.response().unwrap().status, 503);
+}
+
+/// error of a custom transport
+fn transport_error(transient: bool) -> QueryError {
+    QueryError::Transport {
+        error: Box::new(std::io::Error::new(std::io::ErrorKind::TimedOut, "proxy timed out")),
+        transient,
+    }
+}
+
+#[tokio::test]
+async fn retry_transient_transport_error() {
+    let transport = Arc::new(FakeTransport::with_results(vec![
+        Err(transport_error(true)),
+        Ok(response(200, &[], VERSIONS)),
+    ]));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_retry(RetryPolicy::new().backoff_base(Duration::ZERO))
+        .build()
+        .unwrap();
+
+    let versions = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(versions.latest_version.as_deref(), Some("1.0.0"));
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn no_retry_permanent_transport_error() {
+    let transport = Arc::new(FakeTransport::with_results(vec![Err(transport_error(false))]));
+
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_transport(transport.clone())
+        .with_retry(RetryPolicy::new().backoff_base(Duration::ZERO))
+        .build()
+        .unwrap();
+
+    let error = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap_err();
+    assert!(matches!(error, QueryError::Transport { transient: false, .. }));
+    assert_eq!(transport.requests.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn redacted_authorization_header() {
+    let request = HttpRequest {
+        method: RequestMethod::POST,
+        url: "https://release-monitoring.org/api/v2/projects/".parse().unwrap(),
+        headers: vec![
+            (String::from("Accept"), String::from("application/json")),
+            (String::from("Authorization"), String::from("token s3cr3t")),
+        ],
+        body: None,
+    };
+
+    let debug = format!("{:?}", request);
+    assert!(!debug.contains("s3cr3t"));
+    assert!(debug.contains("application/json"));
+    assert_eq!(request.header("authorization"), Some("token s3cr3t"));
+}
@@ -0,0 +1,74 @@
+use anitya::v1::{
+    DistroNamesQuery,
+    EcosystemProjectQuery,
+    PackageProjectQuery,
+    ProjectIdQuery,
+    ProjectNamesQuery,
+    ProjectsQuery,
+    VersionCheckRequest,
+};
+use anitya::{Ecosystem, ProjectId};
+
+mod common;
+
+#[tokio::test]
+async fn v1_projects() {
+    let client = common::builder("v1_projects").build().unwrap();
+
+    let query = ProjectsQuery::new().pattern(String::from("requests*"));
+    let projects = client.request(&query).await.unwrap();
+    assert_eq!(projects.len(), 2);
+    assert!(projects.iter().all(|project| project.packages.is_empty()));
+
+    let query = ProjectsQuery::new().homepage(String::from("https://pypi.org/project/requests"));
+    let projects = client.request(&query).await.unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].id, ProjectId(4004));
+}
+
+#[tokio::test]
+async fn v1_names() {
+    let client = common::builder("v1_names").build().unwrap();
+
+    let query = ProjectNamesQuery::new().pattern(String::from("requests*"));
+    let names = client.request(&query).await.unwrap();
+    assert_eq!(names, vec!["requests", "requests-oauthlib"]);
+
+    let names = client.request(&DistroNamesQuery::new()).await.unwrap();
+    assert!(names.contains(&String::from("Fedora")));
+}
+
+#[tokio::test]
+async fn v1_project() {
+    let client = common::builder("v1_project").build().unwrap();
+
+    let project = client.request(&ProjectIdQuery::new(ProjectId(4004))).await.unwrap();
+    assert_eq!(project.name, "requests");
+    assert_eq!(project.packages.len(), 3);
+    assert_eq!(project.packages[0].distro, "Fedora");
+    assert_eq!(project.packages[0].package_name, "python-requests");
+
+    let query = PackageProjectQuery::new(String::from("Fedora"), String::from("python-requests"));
+    let project = client.request(&query).await.unwrap();
+    assert_eq!(project.id, ProjectId(4004));
+
+    // ecosystem and project names are percent-encoded in the request path
+    let query = EcosystemProjectQuery::new(
+        Ecosystem::from("https://github.com/foo/bar"),
+        String::from("github.com/foo/bar"),
+    );
+    let project = client.request(&query).await.unwrap();
+    assert_eq!(project.id, ProjectId(13254));
+}
+
+#[tokio::test]
+async fn v1_version_check() {
+    // checking for new versions does not require an API token
+    let client = common::builder("v1_version_check").build().unwrap();
+
+    let project = client
+        .request(&VersionCheckRequest::new(ProjectId(4004)))
+        .await
+        .unwrap();
+    assert_eq!(project.version.as_deref(), Some("2.32.3"));
+}
@@ -0,0 +1,105 @@
+use futures::future::BoxFuture;
+
+use anitya::transport::{HttpRequest, HttpResponse, Transport};
+use anitya::v2::{NewProjectRequest, TemporaryProjectRequest};
+use anitya::{Backend, ClientBuilder, Diagnostic, QueryError, SingleRequest, VersionScheme};
+
+/// transport for requests that must never be sent
+#[derive(Debug)]
+struct UnreachableTransport;
+
+impl Transport for UnreachableTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse, QueryError>> {
+        panic!("Unexpected request: {:?} {}", request.method, request.url);
+    }
+}
+
+fn fields(diagnostics: &[Diagnostic]) -> Vec<&'static str> {
+    diagnostics.iter().map(|diagnostic| diagnostic.field).collect()
+}
+
+#[test]
+fn validate_new_project() {
+    let request = NewProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("fedora-infra/anitya"))
+    .version_prefix(String::from("v"));
+    assert!(request.validate().is_ok());
+
+    // all problems are reported at once
+    let request = NewProjectRequest::new(
+        String::from(" "),
+        String::from("github.com/fedora-infra/anitya"),
+        Backend::from("Github"),
+    )
+    .version_url(String::from("https://github.com/fedora-infra/anitya"))
+    .version_prefix(String::from("v;"))
+    .regex(String::from("anitya-([0-9.]+"));
+    let diagnostics = request.validate().unwrap_err();
+    assert_eq!(
+        fields(&diagnostics),
+        vec!["name", "homepage", "backend", "version_prefix", "regex"]
+    );
+}
+
+#[test]
+fn validate_missing_version_url() {
+    // the GitHub backend needs a version URL to find the repository
+    let request = NewProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+        Backend::GitHub,
+    );
+    let diagnostics = request.validate().unwrap_err();
+    assert_eq!(fields(&diagnostics), vec!["version_url"]);
+
+    // other backends work without one
+    let request = NewProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://pypi.org/project/anitya"),
+        Backend::PyPI,
+    );
+    assert!(request.validate().is_ok());
+}
+
+#[test]
+fn validate_temporary_project() {
+    let request = TemporaryProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("fedora-infra"))
+    .version_scheme(VersionScheme::from("semver"))
+    .version_filter(String::from("rc;;beta"));
+    let diagnostics = request.validate().unwrap_err();
+    assert_eq!(
+        fields(&diagnostics),
+        vec!["version_url", "version_scheme", "version_filter"]
+    );
+}
+
+#[tokio::test]
+async fn automatic_validation() {
+    let client = ClientBuilder::new("https://release-monitoring.org")
+        .with_token("token")
+        .with_transport(UnreachableTransport)
+        .with_validation(true)
+        .build()
+        .unwrap();
+
+    let request = NewProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("https://github.com/fedora-infra/anitya"));
+
+    match client.request(&request).await {
+        Err(QueryError::InvalidRequest { diagnostics }) => assert_eq!(fields(&diagnostics), vec!["version_url"]),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
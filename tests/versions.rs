@@ -1,17 +1,88 @@
 use std::time::Duration;
 
-use anitya::v2::VersionQuery;
-use anitya::ClientBuilder;
+use serde_json::{json, Value};
+
+use anitya::v2::{CheckProjectRequest, NewVersions, TemporaryProjectRequest, VersionQuery};
+use anitya::{Backend, ProjectId, QueryError, SingleRequest, StrictMode, VersionScheme};
+
+mod common;
 
 #[tokio::test]
 async fn version_query() {
     env_logger::builder().filter_level(log::LevelFilter::Debug).init();
 
-    let client = ClientBuilder::new("https://release-monitoring.org")
+    let client = common::builder("version_query")
         .with_delay(Duration::from_millis(1000))
         .build()
         .unwrap();
 
-    let query = VersionQuery::new(7635);
+    let query = VersionQuery::new(ProjectId(7635));
     let _result = client.request(&query).await.unwrap();
 }
+
+#[tokio::test]
+async fn version_query_extra() {
+    // the response contains a field that is not known to this crate
+    let client = common::builder("version_query_extra").build().unwrap();
+    let result = client.request(&VersionQuery::new(ProjectId(7635))).await.unwrap();
+    assert_eq!(result.extra["prerelease_versions"], json!([]));
+
+    let client = common::builder("version_query_extra")
+        .with_strict_mode(StrictMode::Warn)
+        .build()
+        .unwrap();
+    assert!(client.request(&VersionQuery::new(ProjectId(7635))).await.is_ok());
+
+    let client = common::builder("version_query_extra")
+        .with_strict_mode(StrictMode::Error)
+        .build()
+        .unwrap();
+    match client.request(&VersionQuery::new(ProjectId(7635))).await {
+        Err(QueryError::UnknownFields { fields }) => assert_eq!(fields, vec!["prerelease_versions"]),
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
+
+/// JSON body of a request for checking a project for new versions
+fn body<R>(request: &R) -> Value
+where
+    R: SingleRequest<NewVersions, NewVersions>,
+{
+    serde_json::from_str(&request.body().unwrap().unwrap()).unwrap()
+}
+
+#[test]
+fn version_check_bodies() {
+    let request = CheckProjectRequest::with_id(ProjectId(7635));
+    assert_eq!(body(&request), json!({"id": 7635}));
+
+    let request = CheckProjectRequest::with_name_and_homepage(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+    )
+    .dry_run(true);
+    assert_eq!(
+        body(&request),
+        json!({"name": "anitya", "homepage": "https://github.com/fedora-infra/anitya", "dry_run": true})
+    );
+
+    // temporary projects are always checked as dry runs
+    let request = TemporaryProjectRequest::new(
+        String::from("anitya"),
+        String::from("https://github.com/fedora-infra/anitya"),
+        Backend::GitHub,
+    )
+    .version_url(String::from("fedora-infra/anitya"))
+    .version_scheme(VersionScheme::Semantic);
+    assert_eq!(
+        body(&request),
+        json!({
+            "name": "anitya",
+            "homepage": "https://github.com/fedora-infra/anitya",
+            "backend": "GitHub",
+            "dry_run": true,
+            "version_url": "fedora-infra/anitya",
+            "version_scheme": "Semantic"
+        })
+    );
+}